@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use crate::decision_tree::DecisionTreeClassifier;
+use crate::one_r::OneRClassifier;
+use crate::util::accuracy;
+
+pub enum WeakLearner {
+    // дерево рішень глибини 1
+    Stump,
+    OneR,
+}
+
+enum FittedLearner {
+    Stump(DecisionTreeClassifier),
+    OneR(OneRClassifier),
+}
+
+impl FittedLearner {
+    fn predict(&self, data: &[Vec<String>]) -> Vec<String> {
+        match self {
+            FittedLearner::Stump(tree) => tree.predict(data),
+            FittedLearner::OneR(model) => model.predict(data),
+        }
+    }
+}
+
+// Багатокласовий AdaBoost (SAMME, Zhu et al. 2009)
+pub struct AdaBoostClassifier {
+    n_estimators: usize,
+    learning_rate: f64,
+    weak_learner: WeakLearner,
+    learners: Vec<FittedLearner>,
+    learner_weights: Vec<f64>,  // alpha кожного слабкого класифікатора
+    learner_errors: Vec<f64>,  // зважена помилка на навчальній вибірці
+    classes: Vec<String>,
+}
+
+impl AdaBoostClassifier {
+    pub fn new(n_estimators: usize, learning_rate: f64, weak_learner: WeakLearner) -> Self {
+        AdaBoostClassifier {
+            n_estimators,
+            learning_rate,
+            weak_learner,
+            learners: Vec::new(),
+            learner_weights: Vec::new(),
+            learner_errors: Vec::new(),
+            classes: Vec::new(),
+        }
+    }
+
+    pub fn fit(&mut self, x: &[Vec<String>], y: &[String]) {
+        let n = y.len();
+        let mut classes: Vec<String> = y.to_vec();
+        classes.sort();
+        classes.dedup();
+        let k = classes.len() as f64;
+        self.classes = classes;
+        self.learners.clear();
+        self.learner_weights.clear();
+        self.learner_errors.clear();
+
+        let mut weights = vec![1.0 / n as f64; n];
+        for _ in 0..self.n_estimators {
+            let learner = match self.weak_learner {
+                WeakLearner::Stump => {
+                    let mut stump = DecisionTreeClassifier::new().with_max_depth(1);
+                    stump.fit_weighted(x, y, &weights);
+                    FittedLearner::Stump(stump)
+                }
+                WeakLearner::OneR => {
+                    let mut model = OneRClassifier::new();
+                    model.fit_weighted(x, y, &weights);
+                    FittedLearner::OneR(model)
+                }
+            };
+            let predictions = learner.predict(x);
+            let missed: Vec<bool> = predictions.iter().zip(y.iter()).map(|(p, t)| p != t).collect();
+            let total_weight: f64 = weights.iter().sum();
+            let error = missed.iter().zip(weights.iter())
+                .filter(|(&m, _)| m)
+                .map(|(_, w)| w)
+                .sum::<f64>() / total_weight;
+
+            // Класифікатор не кращий за випадкове вгадування серед k класів - зупиняємось
+            if error >= 1.0 - 1.0 / k {
+                if self.learners.is_empty() {
+                    self.learners.push(learner);
+                    self.learner_weights.push(1.0);
+                    self.learner_errors.push(error);
+                }
+                break;
+            }
+
+            // Ідеальний класифікатор - далі підсилювати нічого
+            if error <= 0.0 {
+                self.learners.push(learner);
+                self.learner_weights.push(1.0);
+                self.learner_errors.push(error);
+                break;
+            }
+
+            let alpha = self.learning_rate * (((1.0 - error) / error).ln() + (k - 1.0).ln());
+            for (w, &m) in weights.iter_mut().zip(missed.iter()) {
+                if m {
+                    *w *= alpha.exp();
+                }
+            }
+            let sum: f64 = weights.iter().sum();
+            weights.iter_mut().for_each(|w| *w /= sum);
+
+            self.learners.push(learner);
+            self.learner_weights.push(alpha);
+            self.learner_errors.push(error);
+        }
+    }
+
+    fn vote(&self, scores: &HashMap<String, f64>) -> String {
+        self.classes.iter()
+            .max_by(|a, b| {
+                let sa = scores.get(*a).copied().unwrap_or(0.0);
+                let sb = scores.get(*b).copied().unwrap_or(0.0);
+                sa.partial_cmp(&sb).unwrap_or(std::cmp::Ordering::Equal).then_with(|| b.cmp(a))
+            })
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn predict(&self, data: &[Vec<String>]) -> Vec<String> {
+        self.staged_predict(data).pop().unwrap_or_default()
+    }
+
+    // Прогнози після кожного раунду бустингу: staged[m] - ансамбль з перших m + 1 класифікаторів
+    pub fn staged_predict(&self, data: &[Vec<String>]) -> Vec<Vec<String>> {
+        let mut scores: Vec<HashMap<String, f64>> = vec![HashMap::new(); data.len()];
+        let mut staged = Vec::with_capacity(self.learners.len());
+        for (learner, &alpha) in self.learners.iter().zip(self.learner_weights.iter()) {
+            for (score, class) in scores.iter_mut().zip(learner.predict(data)) {
+                *score.entry(class).or_default() += alpha;
+            }
+            staged.push(scores.iter().map(|score| self.vote(score)).collect());
+        }
+        staged
+    }
+
+    // Помилка (1 - accuracy) після кожного раунду - для графіка помилки від кількості раундів
+    pub fn staged_error(&self, x: &[Vec<String>], y: &[String]) -> Vec<f64> {
+        self.staged_predict(x)
+            .iter()
+            .map(|predictions| 1.0 - accuracy(y, predictions))
+            .collect()
+    }
+
+    pub fn learner_weights(&self) -> &[f64] {
+        &self.learner_weights
+    }
+
+    pub fn learner_errors(&self) -> &[f64] {
+        &self.learner_errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samme_error_falls_to_zero_on_majority_function() {
+        // клас - більшість із трьох бінарних ознак: жоден пеньок не розв'язує задачу сам, а три зважені - так
+        let mut x = Vec::new();
+        let mut y = Vec::new();
+        for code in 0..8 {
+            let bits: Vec<bool> = (0..3).map(|bit| code >> bit & 1 == 1).collect();
+            x.push(bits.iter().map(|&b| if b { "y" } else { "n" }.to_string()).collect::<Vec<String>>());
+            y.push(if bits.iter().filter(|&&b| b).count() >= 2 { "yes" } else { "no" }.to_string());
+        }
+        for weak_learner in [WeakLearner::Stump, WeakLearner::OneR] {
+            let mut model = AdaBoostClassifier::new(10, 1.0, weak_learner);
+            model.fit(&x, &y);
+            let errors = model.staged_error(&x, &y);
+            assert_eq!(errors[0], 0.25);
+            assert_eq!(*errors.last().unwrap(), 0.0);
+            assert!(errors.windows(2).any(|pair| pair[1] < pair[0]));
+            assert_eq!(model.predict(&x), y);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeSet;
+use crate::imputer::is_missing;
+
+// Умова правила "ознака = значення" (для числових ознак OneR значення - мітка інтервалу)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Condition {
+    pub feature: usize,
+    pub value: String,
+}
+
+impl Condition {
+    // Пропуск не задовольняє жодну умову
+    pub fn matches(&self, row: &[String]) -> bool {
+        row.get(self.feature).is_some_and(|value| !is_missing(value) && *value == self.value)
+    }
+
+    pub fn describe(&self, feature_names: &[&str]) -> String {
+        let name = feature_names.get(self.feature).map(|name| name.to_string()).unwrap_or_else(|| format!("#{}", self.feature));
+        if self.value.is_empty() {
+            format!("{name} is missing")
+        } else if self.value.starts_with('<') || self.value.starts_with('>') {
+            format!("{name} {}", self.value)
+        } else if self.value.starts_with('(') {
+            format!("{name} in {}", self.value)
+        } else {
+            format!("{name} = {}", self.value)
+        }
+    }
+}
+
+// Правило "IF умови THEN клас"; coverage - вага прикладів, які дійшли до правила у списку і задовольняють його умови,
+// correct - скільки з них правило класифікує правильно
+#[derive(Clone, Debug)]
+pub struct DecisionRule {
+    pub conditions: Vec<Condition>,
+    pub class: String,
+    pub coverage: f64,
+    pub correct: f64,
+}
+
+impl DecisionRule {
+    pub fn new(conditions: Vec<Condition>, class: String) -> Self {
+        DecisionRule { conditions, class, coverage: 0.0, correct: 0.0 }
+    }
+
+    pub fn covers(&self, row: &[String]) -> bool {
+        self.conditions.iter().all(|condition| condition.matches(row))
+    }
+}
+
+// Упорядкований список правил: спрацьовує перше правило, що покриває приклад, інакше - клас за замовчуванням
+pub fn predict_decision_list(rules: &[DecisionRule], default_class: &str, data: &[Vec<String>]) -> Vec<String> {
+    data.iter()
+        .map(|row| rules.iter().find(|rule| rule.covers(row)).map(|rule| rule.class.clone()).unwrap_or_else(|| default_class.to_string()))
+        .collect()
+}
+
+// Заповнює coverage / correct правил на навчальній вибірці з урахуванням порядку списку
+pub fn update_coverage(rules: &mut [DecisionRule], x: &[Vec<String>], y: &[String]) {
+    for rule in rules.iter_mut() {
+        rule.coverage = 0.0;
+        rule.correct = 0.0;
+    }
+    for (row, label) in x.iter().zip(y) {
+        if let Some(rule) = rules.iter_mut().find(|rule| rule.covers(row)) {
+            rule.coverage += 1.0;
+            if rule.class == *label {
+                rule.correct += 1.0;
+            }
+        }
+    }
+}
+
+// Усі умови "ознака = значення", які виконуються хоча б для одного з рядків rows, на ознаках, яких ще немає серед used;
+// у порядку (ознака, значення), щоб вибір між рівноцінними умовами не залежав від даних
+pub fn candidate_conditions(x: &[Vec<String>], rows: &[usize], used: &[Condition]) -> Vec<Condition> {
+    let mut candidates: BTreeSet<(usize, &str)> = BTreeSet::new();
+    for &i in rows {
+        for (feature, value) in x[i].iter().enumerate() {
+            if !is_missing(value) && used.iter().all(|condition| condition.feature != feature) {
+                candidates.insert((feature, value));
+            }
+        }
+    }
+    candidates.into_iter().map(|(feature, value)| Condition { feature, value: value.to_string() }).collect()
+}
+
+pub fn print_decision_list(rules: &[DecisionRule], default_class: &str, feature_names: &[&str]) {
+    for (i, rule) in rules.iter().enumerate() {
+        let conditions: Vec<String> = rule.conditions.iter().map(|condition| condition.describe(feature_names)).collect();
+        let keyword = if i == 0 { "IF" } else { "ELSE IF" };
+        let conditions = if conditions.is_empty() { "true".to_string() } else { conditions.join(" AND ") };
+        println!("{keyword} {conditions} THEN {} (correct {}/{})", rule.class, rule.correct, rule.coverage);
+    }
+    if rules.is_empty() {
+        println!("{default_class}");
+    } else {
+        println!("ELSE {default_class}");
+    }
+}
@@ -1,7 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use crate::util::Rng;
 
 pub enum Node {
-    Leaf(String),
+    Leaf {
+        class: String,
+        distribution: HashMap<String, f64>,  // частки класів серед навчальних прикладів листка
+    },
     Decision {
         feature_index: usize,
         branches: HashMap<String, Box<Node>>,
@@ -11,8 +16,8 @@ pub enum Node {
 impl Node {
     pub fn print_tree(&self, feature_names: &[&str], indent: &str) {
         match self {
-            Node::Leaf(label) => {
-                println!("{}└── [Leaf] Class: {}", indent, label);
+            Node::Leaf { class, .. } => {
+                println!("{}└── [Leaf] Class: {}", indent, class);
             }
             Node::Decision { feature_index, branches } => {
                 println!("{}└── [Decision] Feature: {}", indent, feature_names[*feature_index]);
@@ -23,75 +28,146 @@ impl Node {
             }
         }
     }
+
+    fn single_class(class: &str) -> Node {
+        Node::Leaf {
+            class: class.to_string(),
+            distribution: HashMap::from([(class.to_string(), 1.0)]),
+        }
+    }
+
+    // Листок з переважним (за сумою ваг) класом підмножини та розподілом класів у ній
+    fn majority_leaf(y: &[String], weights: &[f64], indices: &[usize]) -> Node {
+        let mut subset_class_weight: HashMap<String, f64> = HashMap::new();
+        for &i in indices {
+            *subset_class_weight.entry(y[i].clone()).or_default() += weights[i];
+        }
+        let class = subset_class_weight.iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal))
+            .unwrap().0.clone();
+        let total: f64 = subset_class_weight.values().sum();
+        let distribution = subset_class_weight.into_iter()
+            .map(|(label, weight)| (label, if total > 0.0 { weight / total } else { 0.0 }))
+            .collect();
+        Node::Leaf { class, distribution }
+    }
+}
+
+// Випадковий вибір підмножини ознак у кожному вузлі (для випадкового лісу)
+pub struct FeatureSampler {
+    max_features: usize,
+    rng: Rng,
+}
+
+impl FeatureSampler {
+    pub fn new(max_features: usize, seed: u64) -> Self {
+        FeatureSampler {
+            max_features: max_features.max(1),
+            rng: Rng::new(seed),
+        }
+    }
+
+    fn sample(&mut self, feature_indices: &[usize]) -> Vec<usize> {
+        let mut candidates = feature_indices.to_vec();
+        self.rng.shuffle(&mut candidates);
+        candidates.truncate(self.max_features);
+        candidates
+    }
 }
 
 pub struct DecisionTreeClassifier {
     root: Node,
     default_class: String,  // глобальний клас за замовчуванням (наприклад, найбільш частий у навчанні)
+    feature_gains: Vec<f64>,  // сумарний зважений інформаційний приріст кожної ознаки
+    feature_sampler: Option<FeatureSampler>,  // якщо задано - у вузлі розглядається лише випадкова підмножина ознак
+    max_depth: Option<usize>,  // обмеження глибини (Some(1) - решаючий пеньок)
+}
+
+impl Default for DecisionTreeClassifier {
+    fn default() -> Self {
+        DecisionTreeClassifier::new()
+    }
 }
 
 impl DecisionTreeClassifier {
     pub fn new() -> Self {
         DecisionTreeClassifier {
             // Спочатку корінь можна тимчасово зробити листком з пустим класом
-            root: Node::Leaf(String::new()),
+            root: Node::single_class(""),
             default_class: String::new(),
+            feature_gains: Vec::new(),
+            feature_sampler: None,
+            max_depth: None,
         }
     }
 
-    pub fn fit(&mut self, x: &Vec<Vec<String>>, y: &Vec<String>) {
+    pub fn with_feature_sampler(mut self, sampler: FeatureSampler) -> Self {
+        self.feature_sampler = Some(sampler);
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn fit(&mut self, x: &[Vec<String>], y: &[String]) {
+        self.fit_weighted(x, y, &vec![1.0; y.len()]);
+    }
+
+    // Навчання з вагами прикладів (використовується в AdaBoost)
+    pub fn fit_weighted(&mut self, x: &[Vec<String>], y: &[String], sample_weight: &[f64]) {
         // Обчислити глобальний переважний клас (для випадку непередбачених значень)
-        let mut class_count = HashMap::new();
-        for label in y {
-            *class_count.entry(label.clone()).or_insert(0) += 1;
+        let mut class_weight: HashMap<&String, f64> = HashMap::new();
+        for (label, &weight) in y.iter().zip(sample_weight) {
+            *class_weight.entry(label).or_default() += weight;
         }
-        if let Some((majority_class, _)) = class_count.iter().max_by_key(|entry| entry.1) {
-            self.default_class = majority_class.clone();
+        if let Some((majority_class, _)) = class_weight.iter().max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal)) {
+            self.default_class = (*majority_class).clone();
         }
 
         // Рекурсивно побудувати дерево
         let all_indices: Vec<usize> = (0..y.len()).collect();
         let feature_indices: Vec<usize> = (0..x[0].len()).collect();
-        self.root = self.build_tree(x, y, &all_indices, &feature_indices);
+        self.feature_gains = vec![0.0; x[0].len()];
+        self.root = self.build_tree(x, y, sample_weight, &all_indices, &feature_indices, 0);
     }
 
-    fn build_tree(&self, x: &Vec<Vec<String>>, y: &Vec<String>, indices: &[usize], feature_indices: &[usize]) -> Node {
+    fn build_tree(&mut self, x: &[Vec<String>], y: &[String], w: &[f64], indices: &[usize], feature_indices: &[usize], depth: usize) -> Node {
         // 1. Якщо всі приклади одного класу - повертати Leaf
         let first_class = &y[indices[0]];
         let all_same_class = indices.iter().all(|&i| &y[i] == first_class);
         if all_same_class {
-            return Node::Leaf(first_class.clone());
+            return Node::single_class(first_class);
         }
         /*
             Якщо в поточній підмножині всі приклади мають один і той же клас — ми не можемо нічого кращого придумати,
             створюємо листок з цим класом.
         */
 
-        // 2. Якщо не залишилось ознак - повернути Leaf з переважним класом цієї підмножини
-        if feature_indices.is_empty() {
+        // 2. Якщо не залишилось ознак (або досягнуто максимальної глибини) - повернути Leaf з переважним класом цієї підмножини
+        if feature_indices.is_empty() || self.max_depth.is_some_and(|d| depth >= d) {
             // Знайти найчастіший клас серед indices
-            let mut subset_class_count = HashMap::new();
-            for &i in indices {
-                *subset_class_count.entry(y[i].clone()).or_insert(0) += 1;
-            }
-            let majority_class = subset_class_count.into_iter().max_by_key(|entry| entry.1).unwrap().0;
-            return Node::Leaf(majority_class);
+            return Node::majority_leaf(y, w, indices);
         }
         /*
             Всі ознаки вже використані, а класи все ще змішані → нема сенсу далі розбивати,
             обираємо найпоширеніший клас.
         */
 
+        // Сума ваг прикладів (без ваг - просто кількість)
+        let weight_of = |idxs: &[usize]| idxs.iter().map(|&i| w[i]).sum::<f64>();
+
         // Функція для обчислення ентропії списку індексів
         let entropy = |idxs: &[usize]| {
-            let total = idxs.len() as f64;
-            let mut class_count = HashMap::new();
+            let total = weight_of(idxs);
+            let mut class_weight: HashMap<&String, f64> = HashMap::new();
             for &i in idxs {
-                *class_count.entry(y[i].clone()).or_insert(0_usize) += 1;
+                *class_weight.entry(&y[i]).or_default() += w[i];
             }
             let mut ent = 0.0;
-            for &count in class_count.values() {
-                let p = count as f64 / total;
+            for &weight in class_weight.values() {
+                let p = weight / total;
                 if p > 0.0 {
                     ent -= p * p.log2();
                 }
@@ -100,18 +176,23 @@ impl DecisionTreeClassifier {
         };
 
         let base_entropy = entropy(indices); // рахуємо ентропію для усіх індексів
+        let node_weight = weight_of(indices);
 
         // 3. Знайти ознаку з максимальним інформаційним приростом (найкраща ознака для розбиття)
         let mut best_feature = None;
         let mut best_info_gain = 0.0;
         let mut best_splits: HashMap<String, Vec<usize>> = HashMap::new();
-        for &feature in feature_indices {
+        let candidate_features = match self.feature_sampler.as_mut() {
+            Some(sampler) => sampler.sample(feature_indices),
+            None => feature_indices.to_vec(),
+        };
+        for feature in candidate_features {
             // Розбити indices за значеннями ознаки feature - тобто на групи за значенняи фічі
             let mut splits: HashMap<String, Vec<usize>> = HashMap::new();
             for &i in indices { // i - індекс прикладу
                 let value = &x[i][feature]; // feature - індекс фічі
                 // value - значення певної ознаки
-                splits.entry(value.clone()).or_insert(Vec::new()).push(i);
+                splits.entry(value.clone()).or_default().push(i);
             }
             // Обчислити ентропію після розбиття
             let mut new_entropy = 0.0;
@@ -120,7 +201,7 @@ impl DecisionTreeClassifier {
                     continue; 
                 }
                 let subset_entropy = entropy(subset_indices); // для них й рахуємо ентропію
-                new_entropy += (subset_indices.len() as f64 / indices.len() as f64) * subset_entropy;
+                new_entropy += (weight_of(subset_indices) / node_weight) * subset_entropy;
             }
             let info_gain = base_entropy - new_entropy;
             if info_gain > best_info_gain {
@@ -132,15 +213,12 @@ impl DecisionTreeClassifier {
 
         // Якщо інформаційний приріст нульовий або не знайдено кращої ознаки – листок з переважним класом
         if best_feature.is_none() || best_info_gain <= 0.0 {
-            let mut subset_class_count = HashMap::new();
-            for &i in indices {
-                *subset_class_count.entry(y[i].clone()).or_insert(0) += 1;
-            }
-            let majority_class = subset_class_count.into_iter().max_by_key(|entry| entry.1).unwrap().0;
-            return Node::Leaf(majority_class);
+            return Node::majority_leaf(y, w, indices);
         }
 
         let best_feature_idx = best_feature.unwrap();
+        // Внесок у важливість ознаки - приріст, зважений сумарною вагою прикладів у вузлі
+        self.feature_gains[best_feature_idx] += node_weight * best_info_gain;
         // 4. Рекурсивно побудувати гілки для кожного значення кращої ознаки
         let mut branches: HashMap<String, Box<Node>> = HashMap::new();
         // Сформувати список доступних ознак для дітей (виключаючи обрану)
@@ -150,10 +228,10 @@ impl DecisionTreeClassifier {
             if subset_indices.is_empty() {
                 // Якщо жодного запису з таким значенням в цій підмножині,
                 // створити листок з глобальним або локальним переважним класом
-                branches.insert(value, Box::new(Node::Leaf(self.default_class.clone())));
+                branches.insert(value, Box::new(Node::single_class(&self.default_class)));
             } else {
                 // Рекурсивно викликати build_tree для підмножини
-                let child_node = self.build_tree(x, y, &subset_indices, &remaining_features);
+                let child_node = self.build_tree(x, y, w, &subset_indices, &remaining_features, depth + 1);
                 branches.insert(value, Box::new(child_node));
             }
         }
@@ -169,10 +247,10 @@ impl DecisionTreeClassifier {
         for features in data {
             // Прогноз для одного прикладу
             let mut node = &self.root;
-            let mut predicted_class = self.default_class.clone();
+            let predicted_class;
             loop {
                 match node {
-                    Node::Leaf(class) => {
+                    Node::Leaf { class, .. } => {
                         predicted_class = class.clone();
                         break;
                     }
@@ -200,7 +278,81 @@ impl DecisionTreeClassifier {
         predictions
     }
 
+    // Розподіл класів у листку, до якого потрапляє приклад (для невідомих значень - default_class)
+    pub fn predict_proba(&self, data: &[Vec<String>]) -> Vec<HashMap<String, f64>> {
+        let mut probabilities = Vec::new();
+        for features in data {
+            let mut node = &self.root;
+            let distribution = loop {
+                match node {
+                    Node::Leaf { distribution, .. } => break distribution.clone(),
+                    Node::Decision { feature_index, branches } => {
+                        match features.get(*feature_index).and_then(|value| branches.get(value)) {
+                            Some(next_node) => node = next_node,
+                            None => break HashMap::from([(self.default_class.clone(), 1.0)]),
+                        }
+                    }
+                }
+            };
+            probabilities.push(distribution);
+        }
+        probabilities
+    }
+
     pub fn print_tree(&self, feature_names: &[&str], indent: &str) {
         self.root.print_tree(feature_names, indent);
     }
+
+    // Важливість ознак за зменшенням ентропії (нормалізована, сума = 1)
+    pub fn feature_importances(&self) -> Vec<f64> {
+        let total: f64 = self.feature_gains.iter().sum();
+        if total <= 0.0 {
+            return vec![0.0; self.feature_gains.len()];
+        }
+        self.feature_gains.iter().map(|g| g / total).collect()
+    }
+
+    // Пари (назва ознаки, важливість), відсортовані за спаданням важливості
+    pub fn named_feature_importances(&self, feature_names: &[&str]) -> Vec<(String, f64)> {
+        let mut named: Vec<(String, f64)> = self.feature_importances()
+            .into_iter()
+            .enumerate()
+            .map(|(i, importance)| (feature_names[i].to_string(), importance))
+            .collect();
+        named.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        named
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(values: &[&str]) -> Vec<Vec<String>> {
+        values.iter().map(|row| row.split_whitespace().map(|value| value.to_string()).collect()).collect()
+    }
+
+    #[test]
+    fn importances_go_to_the_splitting_feature() {
+        // клас повністю визначається першою ознакою, друга - шум
+        let x = rows(&["a p", "a q", "b p", "b q", "a p", "b q"]);
+        let y: Vec<String> = "yes yes no no yes no".split_whitespace().map(|class| class.to_string()).collect();
+        let mut model = DecisionTreeClassifier::new();
+        model.fit(&x, &y);
+        assert_eq!(model.feature_importances(), vec![1.0, 0.0]);
+        let named = model.named_feature_importances(&["letter", "noise"]);
+        assert_eq!(named[0], ("letter".to_string(), 1.0));
+    }
+
+    #[test]
+    fn heavy_example_changes_the_stump() {
+        // без ваг перша ознака помиляється двічі, друга - один раз (на останньому прикладі)
+        let x = rows(&["a p", "a p", "b q", "b q", "a q", "b p", "a q"]);
+        let y: Vec<String> = "yes yes no no no yes yes".split_whitespace().map(|class| class.to_string()).collect();
+        let mut stump = DecisionTreeClassifier::new().with_max_depth(1);
+        stump.fit(&x, &y);
+        assert_eq!(stump.feature_importances(), vec![0.0, 1.0]);
+        stump.fit_weighted(&x, &y, &[1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 5.0]);
+        assert_eq!(stump.feature_importances(), vec![1.0, 0.0]);
+    }
 }
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+
+// Спосіб поділу числової ознаки на інтервали
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Binning {
+    // n інтервалів однакової ширини
+    EqualWidth(usize),
+    // n інтервалів з приблизно однаковою кількістю прикладів
+    EqualFrequency(usize),
+    // інтервали 1R (Holte, 1993): у кожному не менше min_bucket прикладів мажоритарного класу
+    OneR(usize),
+    // рекурсивний поділ за ентропією з критерієм зупинки MDL (Fayyad & Irani, 1993)
+    Mdl,
+}
+
+// Перетворення числових стовпців у мітки інтервалів для OneRClassifier та DecisionTreeClassifier
+pub struct Discretizer {
+    binning: Binning,
+    cut_points: Vec<Vec<f64>>,  // cut_points[ознака] - відсортовані межі інтервалів
+}
+
+// Відсортовані пари (значення, клас) без пропусків
+fn sorted_pairs<'a>(values: &[f64], y: &'a [String]) -> Vec<(f64, &'a String)> {
+    let mut pairs: Vec<(f64, &String)> = values.iter().copied().zip(y.iter()).filter(|(v, _)| !v.is_nan()).collect();
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    pairs
+}
+
+fn majority<'a>(counts: &HashMap<&'a String, f64>) -> Option<(&'a String, f64)> {
+    counts.iter()
+        .max_by(|a, b| a.1.total_cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(class, count)| (*class, *count))
+}
+
+// Межі 1R: інтервал закривається, коли мажоритарний клас має щонайменше min_bucket прикладів
+// і наступне значення належить іншому класу; потім сусідні інтервали з однаковим мажоритарним класом зливаються.
+// Однакові значення ніколи не розділяються.
+pub fn one_r_cut_points(values: &[f64], y: &[String], min_bucket: usize) -> Vec<f64> {
+    weighted_one_r_cut_points(values, y, &vec![1.0; y.len()], min_bucket)
+}
+
+// Те саме з вагами прикладів: приклади рахуються за вагою, а ваги спершу масштабуються до середнього 1,
+// тож min_bucket і далі означає "скільки прикладів середньої ваги"
+pub fn weighted_one_r_cut_points(values: &[f64], y: &[String], sample_weight: &[f64], min_bucket: usize) -> Vec<f64> {
+    let mut triples: Vec<(f64, &String, f64)> = values.iter()
+        .zip(y.iter())
+        .zip(sample_weight.iter())
+        .filter(|((v, _), _)| !v.is_nan())
+        .map(|((&v, class), &w)| (v, class, w))
+        .collect();
+    triples.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    let total_weight: f64 = triples.iter().map(|t| t.2).sum();
+    let scale = if total_weight > 0.0 { triples.len() as f64 / total_weight } else { 0.0 };
+
+    // (мажоритарний клас, найменше значення, найбільше значення)
+    let mut buckets: Vec<(&String, f64, f64)> = Vec::new();
+    let mut counts: HashMap<&String, f64> = HashMap::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < triples.len() {
+        let value = triples[i].0;
+        while i < triples.len() && triples[i].0 == value {
+            *counts.entry(triples[i].1).or_default() += triples[i].2 * scale;
+            i += 1;
+        }
+        let (class, count) = majority(&counts).unwrap();
+        let next_differs = i < triples.len() && triples[i].1 != class;
+        // невеликий допуск на похибку округлення після масштабування ваг
+        if (count >= min_bucket as f64 - 1e-9 && next_differs) || i == triples.len() {
+            buckets.push((class, triples[start].0, triples[i - 1].0));
+            counts.clear();
+            start = i;
+        }
+    }
+
+    let mut merged: Vec<(&String, f64, f64)> = Vec::new();
+    for bucket in buckets {
+        match merged.last_mut() {
+            Some(last) if last.0 == bucket.0 => last.2 = bucket.2,
+            _ => merged.push(bucket),
+        }
+    }
+    merged.windows(2).map(|w| (w[0].2 + w[1].1) / 2.0).collect()
+}
+
+fn entropy(counts: &HashMap<&String, usize>, total: usize) -> f64 {
+    counts.values()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn class_counts<'a>(pairs: &[(f64, &'a String)]) -> HashMap<&'a String, usize> {
+    let mut counts = HashMap::new();
+    for (_, class) in pairs {
+        *counts.entry(*class).or_default() += 1;
+    }
+    counts
+}
+
+// Рекурсивний поділ відсортованого відрізка за мінімальною ентропією; поділ приймається,
+// якщо приріст інформації перевищує (log2(N - 1) + delta) / N
+fn mdl_split(pairs: &[(f64, &String)], cuts: &mut Vec<f64>) {
+    let n = pairs.len();
+    if n < 2 {
+        return;
+    }
+    let total_counts = class_counts(pairs);
+    let total_entropy = entropy(&total_counts, n);
+
+    let mut left: HashMap<&String, usize> = HashMap::new();
+    let mut best: Option<(usize, f64)> = None;
+    for i in 1..n {
+        *left.entry(pairs[i - 1].1).or_default() += 1;
+        if pairs[i].0 == pairs[i - 1].0 {
+            continue;
+        }
+        let right: HashMap<&String, usize> = total_counts.iter().map(|(c, &t)| (*c, t - left.get(c).copied().unwrap_or(0))).collect();
+        let weighted = (i as f64 * entropy(&left, i) + (n - i) as f64 * entropy(&right, n - i)) / n as f64;
+        if best.is_none_or(|(_, e)| weighted < e) {
+            best = Some((i, weighted));
+        }
+    }
+    let Some((split, weighted)) = best else { return };
+
+    let (left_pairs, right_pairs) = pairs.split_at(split);
+    let left_counts = class_counts(left_pairs);
+    let right_counts = class_counts(right_pairs);
+    let k = total_counts.len() as f64;
+    let k1 = left_counts.len() as f64;
+    let k2 = right_counts.len() as f64;
+    let delta = (3f64.powf(k) - 2.0).log2()
+        - (k * total_entropy - k1 * entropy(&left_counts, left_pairs.len()) - k2 * entropy(&right_counts, right_pairs.len()));
+    let gain = total_entropy - weighted;
+    if gain > (((n - 1) as f64).log2() + delta) / n as f64 {
+        mdl_split(left_pairs, cuts);
+        cuts.push((left_pairs[left_pairs.len() - 1].0 + right_pairs[0].0) / 2.0);
+        mdl_split(right_pairs, cuts);
+    }
+}
+
+pub fn mdl_cut_points(values: &[f64], y: &[String]) -> Vec<f64> {
+    let mut cuts = Vec::new();
+    mdl_split(&sorted_pairs(values, y), &mut cuts);
+    cuts
+}
+
+// Номер інтервалу значення серед відсортованих меж (значення на межі - у лівому інтервалі)
+pub fn bin_index(cuts: &[f64], value: f64) -> usize {
+    cuts.partition_point(|&cut| cut < value)
+}
+
+// Межа cuts[i] у мітках: 4 знаки після коми без зайвих нулів, а якщо сусідня межа округлюється так само -
+// стільки знаків, скільки потрібно, щоб мітки сусідніх інтервалів не збігалися
+fn format_cut(cuts: &[f64], i: usize) -> String {
+    let round = |value: f64, digits: usize| format!("{value:.digits$}");
+    let neighbours: Vec<f64> = [i.wrapping_sub(1), i + 1].iter().filter_map(|&j| cuts.get(j).copied()).collect();
+    let digits = (4..=17)
+        .find(|&digits| neighbours.iter().all(|&other| round(other, digits) != round(cuts[i], digits)))
+        .unwrap_or(17);
+    let text = round(cuts[i], digits);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+// Мітка інтервалу з номером bin серед меж cuts
+pub fn interval_label(cuts: &[f64], bin: usize) -> String {
+    match (bin, cuts.len()) {
+        (_, 0) => "all".to_string(),
+        (0, _) => format!("<={}", format_cut(cuts, 0)),
+        (b, n) if b == n => format!(">{}", format_cut(cuts, n - 1)),
+        (b, _) => format!("({}, {}]", format_cut(cuts, b - 1), format_cut(cuts, b)),
+    }
+}
+
+impl Discretizer {
+    pub fn new(binning: Binning) -> Self {
+        Discretizer { binning, cut_points: Vec::new() }
+    }
+
+    // y потрібні лише для OneR та MDL (для EqualWidth / EqualFrequency ігноруються)
+    pub fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        self.cut_points = (0..dims)
+            .map(|d| {
+                let values: Vec<f64> = x.iter().map(|row| row[d]).collect();
+                let mut sorted: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                let mut cuts = match self.binning {
+                    Binning::EqualWidth(bins) => match (sorted.first(), sorted.last()) {
+                        (Some(&min), Some(&max)) => (1..bins).map(|i| min + (max - min) * i as f64 / bins as f64).collect(),
+                        _ => Vec::new(),
+                    },
+                    Binning::EqualFrequency(bins) => (1..bins)
+                        .filter_map(|i| {
+                            let position = sorted.len() * i / bins;
+                            // межа між сусідніми різними значеннями, щоб однакові значення були в одному інтервалі
+                            (position > 0 && position < sorted.len()).then(|| (sorted[position - 1] + sorted[position]) / 2.0)
+                        })
+                        .collect(),
+                    Binning::OneR(min_bucket) => one_r_cut_points(&values, y, min_bucket),
+                    Binning::Mdl => mdl_cut_points(&values, y),
+                };
+                cuts.dedup();
+                cuts
+            })
+            .collect();
+    }
+
+    pub fn cut_points(&self) -> &[Vec<f64>] {
+        &self.cut_points
+    }
+
+    // Номер інтервалу для кожного значення (None - пропуск)
+    pub fn bin_indices(&self, x: &[Vec<f64>]) -> Vec<Vec<Option<usize>>> {
+        x.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(self.cut_points.iter())
+                    .map(|(&value, cuts)| (!value.is_nan()).then(|| bin_index(cuts, value)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Мітки інтервалів як категоріальні значення; пропуск - порожній рядок
+    pub fn transform(&self, x: &[Vec<f64>]) -> Vec<Vec<String>> {
+        self.bin_indices(x)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .zip(self.cut_points.iter())
+                    .map(|(bin, cuts)| bin.map(|b| interval_label(cuts, b)).unwrap_or_default())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn fit_transform(&mut self, x: &[Vec<f64>], y: &[String]) -> Vec<Vec<String>> {
+        self.fit(x, y);
+        self.transform(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::load_numeric_dataset;
+
+    fn labels(classes: &str) -> Vec<String> {
+        classes.split_whitespace().map(|class| class.to_string()).collect()
+    }
+
+    // Температура з числового weather (Witten & Frank): 1R з min_bucket = 3 дає одну межу 77.5
+    #[test]
+    fn one_r_cuts_temperature() {
+        let temperature = [64.0, 65.0, 68.0, 69.0, 70.0, 71.0, 72.0, 72.0, 75.0, 75.0, 80.0, 81.0, 83.0, 85.0];
+        let play = labels("yes no yes yes yes no no yes yes yes no yes yes no");
+        assert_eq!(one_r_cut_points(&temperature, &play, 3), vec![77.5]);
+        // той самий стовпець у MDL не ділиться
+        assert!(mdl_cut_points(&temperature, &play).is_empty());
+    }
+
+    #[test]
+    fn weighted_one_r_cuts() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+        let y = labels("a b a a");
+        assert!(one_r_cut_points(&values, &y, 2).is_empty());
+        // однакові ваги будь-якого масштабу не змінюють меж
+        assert!(weighted_one_r_cut_points(&values, &y, &[0.1; 4], 2).is_empty());
+        // вага 3 для "b" робить його окремим інтервалом
+        assert_eq!(weighted_one_r_cut_points(&values, &y, &[1.0, 3.0, 1.0, 1.0], 2), vec![2.5]);
+    }
+
+    #[test]
+    fn mdl_cuts() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, f64::NAN];
+        assert_eq!(mdl_cut_points(&values, &labels("a a a b b b b")), vec![3.5]);
+
+        // межі для iris такі самі, як у Weka (supervised Discretize)
+        let (x, y, _) = load_numeric_dataset("datasets/iris.csv", 1).unwrap();
+        let column = |d: usize| x.iter().map(|row| row[d]).collect::<Vec<f64>>();
+        assert_eq!(mdl_cut_points(&column(2), &y), vec![2.45, 4.75]);
+        assert_eq!(mdl_cut_points(&column(3), &y), vec![0.8, 1.75]);
+    }
+
+    #[test]
+    fn close_cuts_get_distinct_labels() {
+        assert_eq!((0..3).map(|b| interval_label(&[2.45, 4.75], b)).collect::<Vec<_>>(), vec!["<=2.45", "(2.45, 4.75]", ">4.75"]);
+        let cuts = [1.0000075, 1.00002, 1.0000325];
+        let labels: Vec<String> = (0..=cuts.len()).map(|b| interval_label(&cuts, b)).collect();
+        assert_eq!(labels, vec!["<=1.00001", "(1.00001, 1.00002]", "(1.00002, 1.00003]", ">1.00003"]);
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+
+// Метрика відстані для kNN
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Distance {
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+    Minkowski(f64),
+    // 1 - косинус кута між векторами (не є метрикою, тому тільки повний перебір)
+    Cosine,
+    // з оберненою коваріаційною матрицею навчальних даних
+    Mahalanobis,
+    // частка ознак, що не збігаються (для категоріальних даних)
+    Hamming,
+    // Heterogeneous Euclidean-Overlap Metric (Wilson & Martinez, 1997)
+    Heom,
+    // Heterogeneous Value Difference Metric (Wilson & Martinez, 1997)
+    Hvdm,
+}
+
+impl Distance {
+    // Метрики з сімейства Мінковського - для них працює KD-дерево
+    pub fn is_minkowski(&self) -> bool {
+        matches!(self, Distance::Euclidean | Distance::Manhattan | Distance::Chebyshev | Distance::Minkowski(_))
+    }
+
+    // Чи виконуються аксіоми метрики (потрібно для ball tree). HEOM/HVDM - ні: пропущене значення
+    // дає відстань 1 навіть до самого себе, тому для них лише повний перебір
+    pub fn is_metric(&self) -> bool {
+        match self {
+            Distance::Cosine | Distance::Heom | Distance::Hvdm => false,
+            Distance::Minkowski(p) => *p >= 1.0,
+            _ => true,
+        }
+    }
+
+    // Розбір назви у форматі {:?} (наприклад "Manhattan", "Minkowski(3.0)") - для завантаження моделей
+    pub fn from_name(name: &str) -> Option<Distance> {
+        if let Some(p) = name.strip_prefix("Minkowski(").and_then(|rest| rest.strip_suffix(')')) {
+            return p.parse().ok().map(Distance::Minkowski);
+        }
+        match name {
+            "Euclidean" => Some(Distance::Euclidean),
+            "Manhattan" => Some(Distance::Manhattan),
+            "Chebyshev" => Some(Distance::Chebyshev),
+            "Cosine" => Some(Distance::Cosine),
+            "Mahalanobis" => Some(Distance::Mahalanobis),
+            "Hamming" => Some(Distance::Hamming),
+            "Heom" => Some(Distance::Heom),
+            "Hvdm" => Some(Distance::Hvdm),
+            _ => None,
+        }
+    }
+
+    // Чи потрібні мітки класів для навчання метрики
+    pub fn needs_labels(&self) -> bool {
+        matches!(self, Distance::Hvdm)
+    }
+}
+
+// Метрика з параметрами, навченими на тренувальних даних
+#[derive(Clone)]
+pub struct DistanceMetric {
+    kind: Distance,
+    // ознаки, які закодовані як категорії (порівнюються на рівність)
+    categorical: Vec<bool>,
+    // Mahalanobis: обернена коваріаційна матриця
+    inverse_covariance: Vec<Vec<f64>>,
+    // HEOM: розмах числових ознак; HVDM: 4 * стандартне відхилення
+    scales: Vec<f64>,
+    // HVDM: для кожної категоріальної ознаки значення -> P(клас | значення)
+    value_class_probabilities: Vec<HashMap<u64, Vec<f64>>>,
+    num_classes: usize,
+}
+
+// Обернення матриці методом Гаусса-Жордана (з невеликою регуляризацією діагоналі)
+fn invert_matrix(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = matrix.len();
+    let mut a: Vec<Vec<f64>> = matrix.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut extended = row.clone();
+            extended[i] += 1e-9;
+            extended.extend((0..n).map(|j| if i == j { 1.0 } else { 0.0 }));
+            extended
+        })
+        .collect();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&r1, &r2| a[r1][col].abs().partial_cmp(&a[r2][col].abs()).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap();
+        a.swap(col, pivot);
+        let p = a[col][col];
+        if p.abs() < 1e-12 {
+            continue;
+        }
+        a[col].iter_mut().for_each(|v| *v /= p);
+        let pivot_row = a[col].clone();
+        for (r, row) in a.iter_mut().enumerate() {
+            if r != col {
+                let factor = row[col];
+                row.iter_mut().zip(pivot_row.iter()).for_each(|(v, pv)| *v -= factor * pv);
+            }
+        }
+    }
+    a.into_iter().map(|row| row[n..].to_vec()).collect()
+}
+
+pub fn covariance_matrix(x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = x.len();
+    let dims = x.first().map(|row| row.len()).unwrap_or(0);
+    let means: Vec<f64> = (0..dims).map(|d| x.iter().map(|row| row[d]).sum::<f64>() / n as f64).collect();
+    let mut cov = vec![vec![0.0; dims]; dims];
+    for row in x {
+        for i in 0..dims {
+            for j in 0..dims {
+                cov[i][j] += (row[i] - means[i]) * (row[j] - means[j]) / (n as f64 - 1.0).max(1.0);
+            }
+        }
+    }
+    cov
+}
+
+impl DistanceMetric {
+    // Метрика без навчених параметрів (усі ознаки числові)
+    pub fn new(kind: Distance) -> Self {
+        DistanceMetric {
+            kind,
+            categorical: Vec::new(),
+            inverse_covariance: Vec::new(),
+            scales: Vec::new(),
+            value_class_probabilities: Vec::new(),
+            num_classes: 0,
+        }
+    }
+
+    // categorical[i] = true, якщо i-та ознака - код категорії; y (індекси класів) потрібні лише для HVDM,
+    // без них HVDM замінюється на HEOM (див. kind())
+    pub fn fit(kind: Distance, x: &[Vec<f64>], categorical: &[bool], y: Option<&[usize]>) -> Self {
+        let kind = if kind == Distance::Hvdm && y.is_none() { Distance::Heom } else { kind };
+        let mut metric = DistanceMetric::new(kind);
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        metric.categorical = if categorical.is_empty() { vec![false; dims] } else { categorical.to_vec() };
+
+        let column = |d: usize| x.iter().map(move |row| row[d]).filter(|v| !v.is_nan());
+        match kind {
+            Distance::Mahalanobis => {
+                metric.inverse_covariance = invert_matrix(&covariance_matrix(x));
+            }
+            Distance::Heom => {
+                metric.scales = (0..dims)
+                    .map(|d| {
+                        let min = column(d).fold(f64::INFINITY, f64::min);
+                        let max = column(d).fold(f64::NEG_INFINITY, f64::max);
+                        if max > min { max - min } else { 1.0 }
+                    })
+                    .collect();
+            }
+            Distance::Hvdm => {
+                metric.scales = (0..dims)
+                    .map(|d| {
+                        let count = column(d).count().max(1) as f64;
+                        let mean = column(d).sum::<f64>() / count;
+                        let std = (column(d).map(|v| (v - mean).powi(2)).sum::<f64>() / count).sqrt();
+                        if std > 0.0 { 4.0 * std } else { 1.0 }
+                    })
+                    .collect();
+                let y = y.unwrap_or_default();
+                metric.num_classes = y.iter().copied().max().map(|m| m + 1).unwrap_or(0);
+                metric.value_class_probabilities = (0..dims)
+                    .map(|d| {
+                        let mut counts: HashMap<u64, Vec<f64>> = HashMap::new();
+                        if metric.categorical[d] {
+                            for (row, &class) in x.iter().zip(y.iter()) {
+                                counts.entry(row[d].to_bits()).or_insert_with(|| vec![0.0; metric.num_classes])[class] += 1.0;
+                            }
+                            for probabilities in counts.values_mut() {
+                                let total: f64 = probabilities.iter().sum();
+                                probabilities.iter_mut().for_each(|p| *p /= total);
+                            }
+                        }
+                        counts
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+        metric
+    }
+
+    // Метрика, яка фактично використовується
+    pub fn kind(&self) -> Distance {
+        self.kind
+    }
+
+    fn is_categorical(&self, d: usize) -> bool {
+        self.categorical.get(d).copied().unwrap_or(false)
+    }
+
+    pub fn distance(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self.kind {
+            Distance::Euclidean => a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f64>().sqrt(),
+            Distance::Manhattan => a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum(),
+            Distance::Chebyshev => a.iter().zip(b).map(|(x, y)| (x - y).abs()).fold(0.0, f64::max),
+            Distance::Minkowski(p) => a.iter().zip(b).map(|(x, y)| (x - y).abs().powf(p)).sum::<f64>().powf(1.0 / p),
+            Distance::Cosine => {
+                let dot: f64 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+                let norm_a = a.iter().map(|x| x * x).sum::<f64>().sqrt();
+                let norm_b = b.iter().map(|y| y * y).sum::<f64>().sqrt();
+                if norm_a == 0.0 || norm_b == 0.0 { 1.0 } else { 1.0 - dot / (norm_a * norm_b) }
+            }
+            Distance::Hamming => {
+                let mismatches = a.iter().zip(b).filter(|(x, y)| x != y).count();
+                mismatches as f64 / a.len().max(1) as f64
+            }
+            Distance::Mahalanobis | Distance::Heom | Distance::Hvdm => self.reduced_distance(a, b).sqrt(),
+        }
+    }
+
+    // "Зменшена" відстань - монотонна функція від distance без кореня/степеня 1/p
+    // (для порівняння сусідів: квадрат для евклідової, сума |x - y|^p для Мінковського)
+    pub fn reduced_distance(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self.kind {
+            Distance::Euclidean => {
+                let mut total = 0.0;
+                for (x, y) in a.iter().zip(b) {
+                    let diff = x - y;
+                    total += diff * diff;
+                }
+                total
+            }
+            Distance::Minkowski(p) => a.iter().zip(b).map(|(x, y)| (x - y).abs().powf(p)).sum(),
+            Distance::Mahalanobis => {
+                let diff: Vec<f64> = a.iter().zip(b).map(|(x, y)| x - y).collect();
+                let mut total = 0.0;
+                for (i, row) in self.inverse_covariance.iter().enumerate() {
+                    total += diff[i] * row.iter().zip(diff.iter()).map(|(s, d)| s * d).sum::<f64>();
+                }
+                total.max(0.0)
+            }
+            Distance::Heom | Distance::Hvdm => {
+                a.iter()
+                    .zip(b)
+                    .enumerate()
+                    .map(|(d, (&x, &y))| self.attribute_distance(d, x, y).powi(2))
+                    .sum::<f64>()
+            }
+            _ => self.distance(a, b),
+        }
+    }
+
+    pub fn reduced_to_distance(&self, reduced: f64) -> f64 {
+        match self.kind {
+            Distance::Euclidean | Distance::Mahalanobis | Distance::Heom | Distance::Hvdm => reduced.sqrt(),
+            Distance::Minkowski(p) => reduced.powf(1.0 / p),
+            _ => reduced,
+        }
+    }
+
+    pub fn distance_to_reduced(&self, distance: f64) -> f64 {
+        match self.kind {
+            Distance::Euclidean | Distance::Mahalanobis | Distance::Heom | Distance::Hvdm => distance * distance,
+            Distance::Minkowski(p) => distance.powf(p),
+            _ => distance,
+        }
+    }
+
+    // Відстань по одній ознаці для HEOM/HVDM (пропущене значення - максимальна відстань 1)
+    fn attribute_distance(&self, d: usize, x: f64, y: f64) -> f64 {
+        if x.is_nan() || y.is_nan() {
+            return 1.0;
+        }
+        if !self.is_categorical(d) {
+            return (x - y).abs() / self.scales[d];
+        }
+        if self.kind == Distance::Heom {
+            return if x == y { 0.0 } else { 1.0 };
+        }
+        // normalized_vdm2: невідоме значення відображається у нульовий вектор ймовірностей
+        let zeros = vec![0.0; self.num_classes];
+        let px = self.value_class_probabilities[d].get(&x.to_bits()).unwrap_or(&zeros);
+        let py = self.value_class_probabilities[d].get(&y.to_bits()).unwrap_or(&zeros);
+        px.iter().zip(py.iter()).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt()
+    }
+
+    // Мінімальна зменшена відстань від точки до прямокутника [lower, upper] (лише для метрик Мінковського)
+    pub fn min_reduced_distance_to_box(&self, query: &[f64], lower: &[f64], upper: &[f64]) -> f64 {
+        let gaps = query.iter()
+            .zip(lower.iter().zip(upper.iter()))
+            .map(|(&q, (&lo, &hi))| if q < lo { lo - q } else if q > hi { q - hi } else { 0.0 });
+        match self.kind {
+            Distance::Manhattan => gaps.sum(),
+            Distance::Chebyshev => gaps.fold(0.0, f64::max),
+            Distance::Minkowski(p) => gaps.map(|g| g.powf(p)).sum::<f64>(),
+            _ => gaps.map(|g| g * g).sum::<f64>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hvdm_without_labels_falls_back_to_heom() {
+        let x = vec![vec![0.0, 1.0], vec![0.0, 3.0], vec![1.0, 5.0]];
+        let metric = DistanceMetric::fit(Distance::Hvdm, &x, &[true, false], None);
+        assert_eq!(metric.kind(), Distance::Heom);
+        // категорії: 0 або 1; числова ознака ділиться на розмах 4
+        assert_eq!(metric.distance(&x[0], &x[1]), 0.5);
+        assert_eq!(metric.distance(&x[0], &x[2]), 2.0f64.sqrt());
+        // пропущене значення - відстань 1 навіть до себе, тому HEOM/HVDM не є метриками
+        let missing = [f64::NAN, 1.0];
+        assert_eq!(metric.distance(&missing, &missing), 1.0);
+        assert!(!Distance::Heom.is_metric() && !Distance::Hvdm.is_metric());
+    }
+}
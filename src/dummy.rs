@@ -0,0 +1,212 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+use crate::pipeline::Classifier;
+use crate::util::{accuracy, Rng};
+
+// ZeroR: завжди найчастіший клас навчальної вибірки (ознаки ігноруються) - базовий рівень для порівняння
+pub struct ZeroRClassifier {
+    default_class: String,
+}
+
+impl Default for ZeroRClassifier {
+    fn default() -> Self {
+        ZeroRClassifier::new()
+    }
+}
+
+impl ZeroRClassifier {
+    pub fn new() -> Self {
+        ZeroRClassifier { default_class: String::new() }
+    }
+
+    pub fn fit(&mut self, y: &[String]) {
+        self.fit_weighted(y, &vec![1.0; y.len()]);
+    }
+
+    // При рівності ваг - лексикографічно менший клас
+    pub fn fit_weighted(&mut self, y: &[String], sample_weight: &[f64]) {
+        let mut class_weights: HashMap<&String, f64> = HashMap::new();
+        for (label, &weight) in y.iter().zip(sample_weight) {
+            *class_weights.entry(label).or_default() += weight;
+        }
+        self.default_class = class_weights.iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal).then_with(|| b.0.cmp(a.0)))
+            .map(|(class, _)| (*class).clone())
+            .unwrap_or_default();
+    }
+
+    // Працює з будь-якими рядками даних (категоріальними чи числовими)
+    pub fn predict<T>(&self, data: &[T]) -> Vec<String> {
+        vec![self.default_class.clone(); data.len()]
+    }
+
+    pub fn default_class(&self) -> &str {
+        &self.default_class
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        if lines.next() != Some("zero_r") {
+            return Err("очікувався заголовок zero_r".into());
+        }
+        let default_class = lines.next().and_then(|line| line.strip_prefix("class ")).ok_or("немає рядка class")?;
+        Ok(ZeroRClassifier { default_class: default_class.to_string() })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DummyStrategy {
+    // випадковий клас з розподілом класів навчальної вибірки
+    Stratified,
+    // випадковий клас з рівними ймовірностями
+    Uniform,
+}
+
+// Випадковий класифікатор; predict детермінований для заданого seed
+pub struct DummyClassifier {
+    strategy: DummyStrategy,
+    seed: u64,
+    classes: Vec<String>,
+    priors: Vec<f64>,
+}
+
+impl DummyClassifier {
+    pub fn new(strategy: DummyStrategy, seed: u64) -> Self {
+        DummyClassifier { strategy, seed, classes: Vec::new(), priors: Vec::new() }
+    }
+
+    pub fn fit(&mut self, y: &[String]) {
+        let mut classes: Vec<String> = y.to_vec();
+        classes.sort();
+        classes.dedup();
+        self.priors = match self.strategy {
+            DummyStrategy::Stratified => classes.iter()
+                .map(|class| y.iter().filter(|label| *label == class).count() as f64 / y.len() as f64)
+                .collect(),
+            DummyStrategy::Uniform => vec![1.0 / classes.len() as f64; classes.len()],
+        };
+        self.classes = classes;
+    }
+
+    pub fn predict<T>(&self, data: &[T]) -> Vec<String> {
+        let mut rng = Rng::new(self.seed);
+        (0..data.len())
+            .map(|_| {
+                let mut threshold = rng.next_f64();
+                let chosen = self.priors.iter()
+                    .position(|&p| {
+                        threshold -= p;
+                        threshold < 0.0
+                    })
+                    .unwrap_or(self.classes.len().saturating_sub(1));
+                self.classes.get(chosen).cloned().unwrap_or_default()
+            })
+            .collect()
+    }
+
+    // Очікувана точність на вибірці з мітками y_true: sum p_навч(c) * p_тест(c)
+    pub fn expected_accuracy(&self, y_true: &[String]) -> f64 {
+        self.classes.iter()
+            .zip(self.priors.iter())
+            .map(|(class, p)| p * y_true.iter().filter(|label| *label == class).count() as f64 / y_true.len().max(1) as f64)
+            .sum()
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let mut model = match header.as_slice() {
+            ["dummy", "stratified", seed] => DummyClassifier::new(DummyStrategy::Stratified, seed.parse()?),
+            ["dummy", "uniform", seed] => DummyClassifier::new(DummyStrategy::Uniform, seed.parse()?),
+            _ => return Err(format!("невідомий заголовок dummy: {:?}", header).into()),
+        };
+        for line in lines {
+            let (prior, class) = line.strip_prefix("class ")
+                .and_then(|rest| rest.split_once(' '))
+                .ok_or(format!("очікувався рядок class, отримано: {line}"))?;
+            model.priors.push(prior.parse()?);
+            model.classes.push(class.to_string());
+        }
+        Ok(model)
+    }
+}
+
+// Точність ZeroR, навченого на y_train, на y_test
+pub fn baseline_accuracy(y_train: &[String], y_test: &[String]) -> f64 {
+    let mut zero_r = ZeroRClassifier::new();
+    zero_r.fit(y_train);
+    accuracy(y_test, &zero_r.predict(y_test))
+}
+
+// Відносний приріст точності над базовим рівнем: accuracy / baseline
+pub fn lift(accuracy: f64, baseline: f64) -> f64 {
+    if baseline > 0.0 { accuracy / baseline } else { f64::INFINITY }
+}
+
+// Рядок звіту: точність моделі, її lift та приріст у процентних пунктах над ZeroR
+pub fn print_lift(name: &str, accuracy: f64, baseline: f64) {
+    println!("{:<28} accuracy = {:.4}, lift = {:.2}x, {:+.1} pp over ZeroR", name, accuracy, lift(accuracy, baseline), (accuracy - baseline) * 100.0);
+}
+
+impl Classifier for ZeroRClassifier {
+    fn fit(&mut self, _x: &[Vec<f64>], y: &[String]) {
+        ZeroRClassifier::fit(self, y);
+    }
+
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        ZeroRClassifier::predict(self, x)
+    }
+
+    fn to_text(&self) -> String {
+        format!("zero_r\nclass {}\n", self.default_class)
+    }
+}
+
+impl Classifier for DummyClassifier {
+    fn fit(&mut self, _x: &[Vec<f64>], y: &[String]) {
+        DummyClassifier::fit(self, y);
+    }
+
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        DummyClassifier::predict(self, x)
+    }
+
+    fn to_text(&self) -> String {
+        let strategy = match self.strategy {
+            DummyStrategy::Stratified => "stratified",
+            DummyStrategy::Uniform => "uniform",
+        };
+        let mut text = format!("dummy {strategy} {}\n", self.seed);
+        for (class, prior) in self.classes.iter().zip(self.priors.iter()) {
+            text.push_str(&format!("class {prior} {class}\n"));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(classes: &str) -> Vec<String> {
+        classes.split_whitespace().map(|class| class.to_string()).collect()
+    }
+
+    #[test]
+    fn baselines_and_lift() {
+        let y = labels("b a a b c");
+        let mut zero_r = ZeroRClassifier::new();
+        zero_r.fit(&y);
+        // рівність ваг "a" і "b" - лексикографічно менший клас
+        assert_eq!(zero_r.default_class(), "a");
+        assert_eq!(baseline_accuracy(&y, &y), 0.4);
+        assert_eq!(lift(0.8, 0.4), 2.0);
+
+        let mut uniform = DummyClassifier::new(DummyStrategy::Uniform, 1);
+        uniform.fit(&y);
+        assert!((uniform.expected_accuracy(&y) - 1.0 / 3.0).abs() < 1e-12);
+        let restored = DummyClassifier::from_text(&Classifier::to_text(&uniform)).unwrap();
+        assert_eq!(restored.predict(&y), uniform.predict(&y));
+    }
+}
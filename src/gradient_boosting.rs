@@ -0,0 +1,297 @@
+use std::error::Error;
+use crate::pipeline::Classifier;
+use crate::preprocessing::{parse_values_line, values_line};
+use crate::regression_tree::DecisionTreeRegressor;
+use crate::util::{select_rows, softmax, train_test_split_indices, Rng};
+
+// Градієнтний бустинг для класифікації: log-loss, softmax по K класах,
+// на кожному раунді K дерев регресії на псевдо-залишках (Friedman, 2001)
+pub struct GradientBoostingClassifier {
+    n_estimators: usize,
+    learning_rate: f64,
+    max_depth: usize,
+    seed: u64,
+    pub subsample: f64,  // частка навчальних прикладів для кожного раунду (stochastic gradient boosting)
+    pub validation_fraction: f64,  // частка прикладів для ранньої зупинки (0 - без ранньої зупинки)
+    pub n_iter_no_change: usize,  // скільки раундів без покращення валідаційної втрати чекати
+    classes: Vec<String>,
+    init_scores: Vec<f64>,
+    trees: Vec<Vec<DecisionTreeRegressor>>,  // trees[раунд][клас]
+    train_loss: Vec<f64>,
+    validation_loss: Vec<f64>,
+}
+
+fn probabilities(scores: &[f64]) -> Vec<f64> {
+    let mut probabilities = scores.to_vec();
+    softmax(&mut probabilities);
+    probabilities
+}
+
+fn log_loss(scores: &[Vec<f64>], targets: &[usize]) -> f64 {
+    let total: f64 = scores.iter()
+        .zip(targets.iter())
+        .map(|(s, &t)| -probabilities(s)[t].max(1e-15).ln())
+        .sum();
+    total / targets.len().max(1) as f64
+}
+
+impl GradientBoostingClassifier {
+    pub fn new(n_estimators: usize, learning_rate: f64, max_depth: usize, seed: u64) -> Self {
+        GradientBoostingClassifier {
+            n_estimators,
+            learning_rate,
+            max_depth,
+            seed,
+            subsample: 1.0,
+            validation_fraction: 0.0,
+            n_iter_no_change: 10,
+            classes: Vec::new(),
+            init_scores: Vec::new(),
+            trees: Vec::new(),
+            train_loss: Vec::new(),
+            validation_loss: Vec::new(),
+        }
+    }
+
+    pub fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        let mut classes: Vec<String> = y.to_vec();
+        classes.sort();
+        classes.dedup();
+        let k = classes.len();
+        self.classes = classes;
+
+        // Відкласти валідаційну частину для ранньої зупинки
+        let (train_idx, val_idx) = if self.validation_fraction > 0.0 {
+            train_test_split_indices(y, self.validation_fraction, self.seed)
+        } else {
+            ((0..y.len()).collect(), Vec::new())
+        };
+        let x_train = select_rows(x, &train_idx);
+        let x_val = select_rows(x, &val_idx);
+        let t_train: Vec<usize> = train_idx.iter().map(|&i| self.class_index(&y[i])).collect();
+        let t_val: Vec<usize> = val_idx.iter().map(|&i| self.class_index(&y[i])).collect();
+        let n = x_train.len();
+
+        // Початкові оцінки - логарифми апріорних ймовірностей класів
+        let mut priors = vec![0.0; k];
+        for &t in &t_train {
+            priors[t] += 1.0 / n as f64;
+        }
+        self.init_scores = priors.iter().map(|p| p.max(1e-15).ln()).collect();
+
+        let mut train_scores = vec![self.init_scores.clone(); n];
+        let mut val_scores = vec![self.init_scores.clone(); x_val.len()];
+        self.trees.clear();
+        self.train_loss.clear();
+        self.validation_loss.clear();
+
+        let mut rng = Rng::new(self.seed);
+        let mut best_val_loss = f64::INFINITY;
+        let mut best_round = 0;
+        for round in 0..self.n_estimators {
+            // Підвибірка без повторень
+            let mut rows: Vec<usize> = (0..n).collect();
+            if self.subsample < 1.0 {
+                rng.shuffle(&mut rows);
+                rows.truncate(((n as f64 * self.subsample).round() as usize).max(1));
+            }
+            let x_sub = select_rows(&x_train, &rows);
+            let probabilities: Vec<Vec<f64>> = rows.iter().map(|&i| probabilities(&train_scores[i])).collect();
+
+            let mut round_trees = Vec::with_capacity(k);
+            for class in 0..k {
+                // Псевдо-залишки: y_ik - p_ik
+                let residuals: Vec<f64> = rows.iter()
+                    .zip(probabilities.iter())
+                    .map(|(&i, p)| (if t_train[i] == class { 1.0 } else { 0.0 }) - p[class])
+                    .collect();
+                let mut tree = DecisionTreeRegressor::new().with_max_depth(self.max_depth);
+                tree.fit_numeric(&x_sub, &residuals);
+
+                // Значення листків - один крок Ньютона для softmax
+                let leaves = tree.apply_numeric(&x_sub);
+                let n_leaves = tree.n_leaves();
+                let mut numerator = vec![0.0; n_leaves];
+                let mut denominator = vec![0.0; n_leaves];
+                for (&leaf, &r) in leaves.iter().zip(residuals.iter()) {
+                    numerator[leaf] += r;
+                    denominator[leaf] += r.abs() * (1.0 - r.abs());
+                }
+                let factor = (k as f64 - 1.0) / k as f64;
+                let values: Vec<f64> = numerator.iter()
+                    .zip(denominator.iter())
+                    .map(|(num, den)| if *den < 1e-12 { 0.0 } else { factor * num / den })
+                    .collect();
+                tree.set_leaf_values(&values);
+
+                for (scores, update) in train_scores.iter_mut().zip(tree.predict_numeric(&x_train)) {
+                    scores[class] += self.learning_rate * update;
+                }
+                for (scores, update) in val_scores.iter_mut().zip(tree.predict_numeric(&x_val)) {
+                    scores[class] += self.learning_rate * update;
+                }
+                round_trees.push(tree);
+            }
+            self.trees.push(round_trees);
+            self.train_loss.push(log_loss(&train_scores, &t_train));
+
+            if !t_val.is_empty() {
+                let val_loss = log_loss(&val_scores, &t_val);
+                self.validation_loss.push(val_loss);
+                if val_loss < best_val_loss - 1e-7 {
+                    best_val_loss = val_loss;
+                    best_round = round;
+                } else if round - best_round >= self.n_iter_no_change {
+                    // Рання зупинка: залишаємо дерева (і криві втрат) до найкращого раунду
+                    self.trees.truncate(best_round + 1);
+                    self.train_loss.truncate(best_round + 1);
+                    self.validation_loss.truncate(best_round + 1);
+                    break;
+                }
+            }
+        }
+    }
+
+    fn class_index(&self, label: &String) -> usize {
+        self.classes.binary_search(label).unwrap()
+    }
+
+    fn decision_function(&self, row: &[f64]) -> Vec<f64> {
+        let mut scores = self.init_scores.clone();
+        for round_trees in &self.trees {
+            for (class, tree) in round_trees.iter().enumerate() {
+                scores[class] += self.learning_rate * tree.predict_one_numeric(row);
+            }
+        }
+        scores
+    }
+
+    // Ймовірності класів у порядку classes()
+    pub fn predict_proba(&self, data: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        data.iter()
+            .map(|row| {
+                let mut scores = self.decision_function(row);
+                softmax(&mut scores);
+                scores
+            })
+            .collect()
+    }
+
+    pub fn predict(&self, data: &[Vec<f64>]) -> Vec<String> {
+        self.predict_proba(data)
+            .iter()
+            .map(|p| {
+                let best = p.iter()
+                    .enumerate()
+                    .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|(i, _)| i)
+                    .unwrap_or(0);
+                self.classes[best].clone()
+            })
+            .collect()
+    }
+
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    pub fn n_rounds(&self) -> usize {
+        self.trees.len()
+    }
+
+    pub fn train_loss(&self) -> &[f64] {
+        &self.train_loss
+    }
+
+    pub fn validation_loss(&self) -> &[f64] {
+        &self.validation_loss
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines().peekable();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let mut model = match header.as_slice() {
+            ["gradient_boosting", n_estimators, learning_rate, max_depth, seed, subsample, validation_fraction, n_iter_no_change] => {
+                let mut model = GradientBoostingClassifier::new(n_estimators.parse()?, learning_rate.parse()?, max_depth.parse()?, seed.parse()?);
+                model.subsample = subsample.parse()?;
+                model.validation_fraction = validation_fraction.parse()?;
+                model.n_iter_no_change = n_iter_no_change.parse()?;
+                model
+            }
+            _ => return Err(format!("невідомий заголовок градієнтного бустингу: {:?}", header).into()),
+        };
+        while let Some(class) = lines.peek().and_then(|line| line.strip_prefix("class ")) {
+            model.classes.push(class.to_string());
+            lines.next();
+        }
+        model.init_scores = parse_values_line(lines.next(), "init")?;
+        if model.init_scores.len() != model.classes.len() {
+            return Err("кількість init не збігається з кількістю класів".into());
+        }
+        while lines.peek().is_some() {
+            let round_trees = (0..model.classes.len())
+                .map(|_| DecisionTreeRegressor::from_lines(&mut lines))
+                .collect::<Result<Vec<_>, _>>()?;
+            model.trees.push(round_trees);
+        }
+        Ok(model)
+    }
+}
+
+// Зберігаються параметри, початкові оцінки та дерева всіх раундів (криві втрат - ні)
+impl Classifier for GradientBoostingClassifier {
+    fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        GradientBoostingClassifier::fit(self, x, y);
+    }
+
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        GradientBoostingClassifier::predict(self, x)
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("gradient_boosting {} {} {} {} {} {} {}\n", self.n_estimators, self.learning_rate, self.max_depth, self.seed,
+                               self.subsample, self.validation_fraction, self.n_iter_no_change);
+        for class in &self.classes {
+            text.push_str(&format!("class {class}\n"));
+        }
+        text.push_str(&values_line("init", &self.init_scores));
+        text.push('\n');
+        for tree in self.trees.iter().flatten() {
+            text.push_str(&tree.to_text());
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::load_classifier;
+    use crate::util::load_numeric_dataset;
+
+    #[test]
+    fn early_stopping_keeps_curves_aligned_with_trees() {
+        let (x, y, _) = load_numeric_dataset("datasets/iris.csv", 1).unwrap();
+        let mut model = GradientBoostingClassifier::new(300, 0.3, 3, 7);
+        model.validation_fraction = 0.3;
+        model.n_iter_no_change = 5;
+        model.fit(&x, &y);
+        assert!(model.n_rounds() < 300);
+        assert_eq!(model.train_loss().len(), model.n_rounds());
+        assert_eq!(model.validation_loss().len(), model.n_rounds());
+    }
+
+    #[test]
+    fn round_trip() {
+        let (x, y, _) = load_numeric_dataset("datasets/iris.csv", 1).unwrap();
+        let mut model = GradientBoostingClassifier::new(30, 0.1, 3, 7);
+        model.subsample = 0.8;
+        model.fit(&x, &y);
+        let text = Classifier::to_text(&model);
+        let restored = GradientBoostingClassifier::from_text(&text).unwrap();
+        assert_eq!(restored.n_rounds(), model.n_rounds());
+        assert_eq!(restored.predict_proba(&x), model.predict_proba(&x));
+        assert_eq!(Classifier::to_text(&restored), text);
+        assert_eq!(load_classifier(&text).unwrap().predict(&x), model.predict(&x));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::util::{accuracy, Rng};
+
+// Permutation importance: наскільки падає точність на відкладеній вибірці,
+// якщо випадково перемішати значення однієї ознаки. Працює з будь-якою моделлю,
+// достатньо передати її predict як замикання.
+pub struct PermutationImportance {
+    pub baseline_accuracy: f64,
+    pub importances_mean: Vec<f64>,
+    pub importances_std: Vec<f64>,
+}
+
+pub fn permutation_importance<T, F>(predict: F, x: &[Vec<T>], y: &[String], n_repeats: usize, seed: u64) -> PermutationImportance
+where
+    T: Clone,
+    F: Fn(&[Vec<T>]) -> Vec<String>,
+{
+    let baseline_accuracy = accuracy(y, &predict(x));
+    let num_features = x.first().map(|row| row.len()).unwrap_or(0);
+    let mut rng = Rng::new(seed);
+
+    let mut importances_mean = Vec::with_capacity(num_features);
+    let mut importances_std = Vec::with_capacity(num_features);
+    let mut shuffled = x.to_vec();
+    for feature in 0..num_features {
+        let mut drops = Vec::with_capacity(n_repeats);
+        for _ in 0..n_repeats {
+            let mut column: Vec<T> = x.iter().map(|row| row[feature].clone()).collect();
+            rng.shuffle(&mut column);
+            for (row, value) in shuffled.iter_mut().zip(column) {
+                row[feature] = value;
+            }
+            drops.push(baseline_accuracy - accuracy(y, &predict(&shuffled)));
+        }
+        // повернути початковий стовпець перед наступною ознакою
+        for (row, original) in shuffled.iter_mut().zip(x.iter()) {
+            row[feature] = original[feature].clone();
+        }
+
+        let mean = drops.iter().sum::<f64>() / drops.len().max(1) as f64;
+        let variance = drops.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / drops.len().max(1) as f64;
+        importances_mean.push(mean);
+        importances_std.push(variance.sqrt());
+    }
+
+    PermutationImportance {
+        baseline_accuracy,
+        importances_mean,
+        importances_std,
+    }
+}
+
+impl PermutationImportance {
+    // Пари (назва ознаки, середнє падіння точності, std), від найважливішої
+    pub fn ranked(&self, feature_names: &[&str]) -> Vec<(String, f64, f64)> {
+        let mut ranked: Vec<(String, f64, f64)> = self.importances_mean.iter()
+            .zip(self.importances_std.iter())
+            .enumerate()
+            .map(|(i, (&mean, &std))| (feature_names[i].to_string(), mean, std))
+            .collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
+
+    // Відбір ознак: усі, що дають падіння точності більше за threshold (альтернатива selected_columns з ноутбука)
+    pub fn selected_features(&self, threshold: f64) -> Vec<usize> {
+        self.importances_mean.iter()
+            .enumerate()
+            .filter(|(_, &mean)| mean > threshold)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shuffling_an_unused_feature_costs_nothing() {
+        // "модель" дивиться лише на першу ознаку, тож друга не має жодного впливу
+        let x: Vec<Vec<String>> = (0..40).map(|i| vec![(i % 2).to_string(), (i % 7).to_string()]).collect();
+        let y: Vec<String> = x.iter().map(|row| row[0].clone()).collect();
+        let predict = |rows: &[Vec<String>]| rows.iter().map(|row| row[0].clone()).collect::<Vec<String>>();
+        let result = permutation_importance(predict, &x, &y, 5, 1);
+        assert_eq!(result.baseline_accuracy, 1.0);
+        assert!(result.importances_mean[0] > 0.2);
+        assert_eq!(result.importances_mean[1], 0.0);
+        assert_eq!(result.importances_std[1], 0.0);
+        assert_eq!(result.selected_features(0.0), vec![0]);
+        assert_eq!(result.ranked(&["signal", "noise"])[0].0, "signal");
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use crate::preprocessing::{column, parse_values_line, quantile, sorted_column, values_line, Transformer};
+
+// Пропуск у числових даних - NaN, у рядкових - порожнє значення або "?"
+pub fn is_missing(value: &str) -> bool {
+    let value = value.trim();
+    value.is_empty() || value == "?"
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Strategy {
+    Mean,
+    Median,
+    MostFrequent,
+    Constant(f64),
+}
+
+// Заповнення пропусків однією статистикою стовпця, обчисленою на навчальних даних
+pub struct SimpleImputer {
+    strategy: Strategy,
+    statistics: Vec<f64>,
+}
+
+// Найчастіше значення відсортованого стовпця (при рівності - менше, як у sklearn)
+fn mode(sorted: &[f64]) -> f64 {
+    let mut best = (f64::NAN, 0);
+    let mut start = 0;
+    while start < sorted.len() {
+        let end = start + sorted[start..].iter().take_while(|&&v| v == sorted[start]).count();
+        if end - start > best.1 {
+            best = (sorted[start], end - start);
+        }
+        start = end;
+    }
+    best.0
+}
+
+// Найчастіше значення (при рівності - менше)
+fn most_frequent<T: Ord + std::hash::Hash>(values: impl Iterator<Item = T>) -> Option<T> {
+    let mut counts: HashMap<T, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts.into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .map(|(value, _)| value)
+}
+
+impl SimpleImputer {
+    pub fn new(strategy: Strategy) -> Self {
+        SimpleImputer { strategy, statistics: Vec::new() }
+    }
+
+    // Значення, якими заповнюються пропуски в кожному стовпці
+    pub fn statistics(&self) -> &[f64] {
+        &self.statistics
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let strategy = match header.as_slice() {
+            ["imputer", "mean"] => Strategy::Mean,
+            ["imputer", "median"] => Strategy::Median,
+            ["imputer", "most_frequent"] => Strategy::MostFrequent,
+            ["imputer", "constant", value] => Strategy::Constant(value.parse()?),
+            _ => return Err(format!("невідомий заголовок імпутації: {:?}", header).into()),
+        };
+        let statistics = parse_values_line(lines.next(), "statistics")?;
+        Ok(SimpleImputer { strategy, statistics })
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        SimpleImputer::from_text(&fs::read_to_string(path)?)
+    }
+}
+
+impl Transformer for SimpleImputer {
+    fn fit(&mut self, x: &[Vec<f64>]) {
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        self.statistics = (0..dims)
+            .map(|d| match self.strategy {
+                Strategy::Mean => {
+                    let values = column(x, d);
+                    values.iter().sum::<f64>() / values.len() as f64
+                }
+                Strategy::Median => quantile(&sorted_column(x, d), 0.5),
+                Strategy::MostFrequent => mode(&sorted_column(x, d)),
+                Strategy::Constant(value) => value,
+            })
+            .collect();
+    }
+
+    // Стовпець, повністю порожній у навчальних даних, так і залишається NaN
+    fn transform(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        x.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(self.statistics.iter())
+                    .map(|(&value, &fill)| if value.is_nan() { fill } else { value })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn to_text(&self) -> String {
+        let header = match self.strategy {
+            Strategy::Mean => "imputer mean".to_string(),
+            Strategy::Median => "imputer median".to_string(),
+            Strategy::MostFrequent => "imputer most_frequent".to_string(),
+            Strategy::Constant(value) => format!("imputer constant {value}"),
+        };
+        format!("{}\n{}\n", header, values_line("statistics", &self.statistics))
+    }
+}
+
+// Заповнення пропусків у категоріальних (рядкових) стовпцях
+pub struct CategoricalImputer {
+    constant: Option<String>,  // None - найчастіше значення стовпця
+    fill: Vec<String>,
+}
+
+impl CategoricalImputer {
+    pub fn most_frequent() -> Self {
+        CategoricalImputer { constant: None, fill: Vec::new() }
+    }
+
+    pub fn constant(value: &str) -> Self {
+        CategoricalImputer { constant: Some(value.to_string()), fill: Vec::new() }
+    }
+
+    pub fn fit(&mut self, x: &[Vec<String>]) {
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        self.fill = (0..dims)
+            .map(|d| match &self.constant {
+                Some(value) => value.clone(),
+                None => most_frequent(x.iter().map(|row| &row[d]).filter(|v| !is_missing(v)))
+                    .cloned()
+                    .unwrap_or_default(),
+            })
+            .collect();
+    }
+
+    pub fn transform(&self, x: &[Vec<String>]) -> Vec<Vec<String>> {
+        x.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(self.fill.iter())
+                    .map(|(value, fill)| if is_missing(value) { fill.clone() } else { value.clone() })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn fit_transform(&mut self, x: &[Vec<String>]) -> Vec<Vec<String>> {
+        self.fit(x);
+        self.transform(x)
+    }
+
+    pub fn fill_values(&self) -> &[String] {
+        &self.fill
+    }
+}
+
+// kNN-імпутація: пропуск заповнюється середнім значенням ознаки серед k найближчих навчальних рядків,
+// у яких ця ознака є. Відстань - nan-euclidean (лише по спільних наявних ознаках, з поправкою на їх кількість).
+pub struct KNNImputer {
+    k: usize,
+    training: Vec<Vec<f64>>,
+    means: Vec<f64>,  // якщо жоден сусід не має ознаки - середнє по навчальних даних
+}
+
+fn nan_euclidean(a: &[f64], b: &[f64]) -> Option<f64> {
+    let mut total = 0.0;
+    let mut present = 0;
+    for (x, y) in a.iter().zip(b) {
+        if !x.is_nan() && !y.is_nan() {
+            total += (x - y) * (x - y);
+            present += 1;
+        }
+    }
+    if present == 0 {
+        None
+    } else {
+        Some((total * a.len() as f64 / present as f64).sqrt())
+    }
+}
+
+impl KNNImputer {
+    pub fn new(k: usize) -> Self {
+        KNNImputer { k, training: Vec::new(), means: Vec::new() }
+    }
+
+    fn impute_row(&self, row: &[f64]) -> Vec<f64> {
+        if row.iter().all(|v| !v.is_nan()) {
+            return row.to_vec();
+        }
+        let mut candidates: Vec<(f64, &Vec<f64>)> = self.training.iter()
+            .filter_map(|other| nan_euclidean(row, other).map(|d| (d, other)))
+            .collect();
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+        row.iter()
+            .enumerate()
+            .map(|(d, &value)| {
+                if !value.is_nan() {
+                    return value;
+                }
+                let donors: Vec<f64> = candidates.iter()
+                    .map(|(_, other)| other[d])
+                    .filter(|v| !v.is_nan())
+                    .take(self.k)
+                    .collect();
+                if donors.is_empty() { self.means[d] } else { donors.iter().sum::<f64>() / donors.len() as f64 }
+            })
+            .collect()
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let k = match header.as_slice() {
+            ["knn_imputer", k] => k.parse()?,
+            _ => return Err(format!("невідомий заголовок kNN-імпутації: {:?}", header).into()),
+        };
+        let training: Vec<Vec<f64>> = lines.map(|line| parse_values_line(Some(line), "row")).collect::<Result<_, _>>()?;
+        let mut imputer = KNNImputer::new(k);
+        imputer.fit(&training);
+        Ok(imputer)
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        KNNImputer::from_text(&fs::read_to_string(path)?)
+    }
+}
+
+impl Transformer for KNNImputer {
+    // Навчальні рядки запам'ятовуються як донори
+    fn fit(&mut self, x: &[Vec<f64>]) {
+        self.training = x.to_vec();
+        let mut mean_imputer = SimpleImputer::new(Strategy::Mean);
+        mean_imputer.fit(x);
+        self.means = mean_imputer.statistics;
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        x.iter().map(|row| self.impute_row(row)).collect()
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("knn_imputer {}\n", self.k);
+        for row in &self.training {
+            text.push_str(&values_line("row", row));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+// Кількість пропусків у стовпці
+pub struct MissingSummary {
+    pub column: String,
+    pub missing: usize,
+    pub fraction: f64,
+}
+
+// mask[i][d] = true, якщо значення пропущене
+pub fn missing_mask(x: &[Vec<f64>]) -> Vec<Vec<bool>> {
+    x.iter().map(|row| row.iter().map(|v| v.is_nan()).collect()).collect()
+}
+
+pub fn missing_report(x: &[Vec<f64>], feature_names: &[String]) -> Vec<MissingSummary> {
+    let mask = missing_mask(x);
+    feature_names.iter()
+        .enumerate()
+        .map(|(d, name)| {
+            let missing = mask.iter().filter(|row| row[d]).count();
+            MissingSummary { column: name.clone(), missing, fraction: missing as f64 / x.len().max(1) as f64 }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knn_imputer_accepts_infinite_values() {
+        // відстань між рядками з нескінченностями - NaN, що не має ламати сортування сусідів
+        let training = vec![vec![1.0, 10.0], vec![2.0, 20.0], vec![f64::INFINITY, 30.0], vec![3.0, f64::NAN]];
+        let mut imputer = KNNImputer::new(2);
+        imputer.fit(&training);
+        let imputed = imputer.transform(&[vec![f64::INFINITY, f64::NAN], vec![2.1, f64::NAN]]);
+        assert!(imputed[0][1].is_finite());
+        assert_eq!(imputed[1][1], 15.0);
+    }
+}
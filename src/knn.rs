@@ -1,8 +1,233 @@
-use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+use std::ops::RangeInclusive;
+use crate::distance::{Distance, DistanceMetric};
+use crate::neighbors::{Algorithm, Neighbor, NeighborIndex};
+use crate::pipeline::Classifier;
+use crate::preprocessing::{parse_values_line, values_line};
+use crate::util::{parallel_map, select_rows, stratified_k_fold};
+
+// Вага голосу сусіда
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Weights {
+    // усі k сусідів голосують однаково
+    Uniform,
+    // вага 1 / відстань (сусіди на нульовій відстані переважають усіх інших)
+    Distance,
+    // гаусове ядро exp(-d^2 / (2 h^2)) з шириною h
+    Gaussian(f64),
+}
+
+impl Weights {
+    pub fn from_name(name: &str) -> Option<Weights> {
+        if let Some(h) = name.strip_prefix("Gaussian(").and_then(|rest| rest.strip_suffix(')')) {
+            return h.parse().ok().map(Weights::Gaussian);
+        }
+        match name {
+            "Uniform" => Some(Weights::Uniform),
+            "Distance" => Some(Weights::Distance),
+            _ => None,
+        }
+    }
+}
+
+// Кодування рядків (категоріальних та змішаних даних) у числа для пошуку сусідів
+struct MixedEncoder {
+    categorical: Vec<bool>,
+    codes: Vec<HashMap<String, f64>>,
+}
+
+impl MixedEncoder {
+    // Стовпець вважається числовим, якщо всі непорожні значення парсяться як f64
+    fn fit(x: &[Vec<String>]) -> Self {
+        let num_features = x.first().map(|row| row.len()).unwrap_or(0);
+        let categorical: Vec<bool> = (0..num_features)
+            .map(|fi| !x.iter().all(|row| row[fi].is_empty() || row[fi].parse::<f64>().is_ok()))
+            .collect();
+        let mut codes = vec![HashMap::new(); num_features];
+        for row in x {
+            for (fi, value) in row.iter().enumerate() {
+                if categorical[fi] && !value.is_empty() {
+                    let next_code = codes[fi].len() as f64;
+                    codes[fi].entry(value.clone()).or_insert(next_code);
+                }
+            }
+        }
+        MixedEncoder { categorical, codes }
+    }
+
+    // Порожнє значення - NaN (пропуск), невідома категорія - -1 (не збігається з жодною відомою)
+    fn transform(&self, x: &[Vec<String>]) -> Vec<Vec<f64>> {
+        x.iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(fi, value)| {
+                        if value.is_empty() {
+                            f64::NAN
+                        } else if self.categorical[fi] {
+                            self.codes[fi].get(value).copied().unwrap_or(-1.0)
+                        } else {
+                            value.parse::<f64>().unwrap_or(f64::NAN)
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Рядок "categorical 0 1 ..." і рядки "code <ознака> <значення>" у порядку кодів
+    fn to_text(&self) -> String {
+        let mut text = String::from("categorical");
+        for &is_categorical in &self.categorical {
+            text.push_str(if is_categorical { " 1" } else { " 0" });
+        }
+        text.push('\n');
+        for (fi, codes) in self.codes.iter().enumerate() {
+            let mut values: Vec<(&String, f64)> = codes.iter().map(|(value, &code)| (value, code)).collect();
+            values.sort_by(|a, b| a.1.total_cmp(&b.1));
+            for (value, _) in values {
+                text.push_str(&format!("code {fi} {value}\n"));
+            }
+        }
+        text
+    }
+
+    // None - у тексті немає рядка categorical (модель навчена на числових даних)
+    fn from_lines(lines: &[&str]) -> Result<Option<Self>, Box<dyn Error>> {
+        let Some(mask) = lines.iter().find_map(|line| line.strip_prefix("categorical")) else { return Ok(None) };
+        let categorical: Vec<bool> = mask.split_whitespace()
+            .map(|flag| match flag {
+                "0" => Ok(false),
+                "1" => Ok(true),
+                _ => Err(format!("некоректна ознака категорії: {flag}")),
+            })
+            .collect::<Result<_, _>>()?;
+        let mut codes = vec![HashMap::new(); categorical.len()];
+        for line in lines.iter().filter_map(|line| line.strip_prefix("code ")) {
+            let (feature, value) = line.split_once(' ').ok_or(format!("некоректний рядок code: {line}"))?;
+            let feature_codes: &mut HashMap<String, f64> = codes.get_mut(feature.parse::<usize>()?).ok_or(format!("немає ознаки {feature}"))?;
+            let next_code = feature_codes.len() as f64;
+            feature_codes.insert(value.to_string(), next_code);
+        }
+        Ok(Some(MixedEncoder { categorical, codes }))
+    }
+}
+
+// Результат підбору k: точність для кожного k та найкраще k
+pub struct KSelection {
+    pub k_values: Vec<usize>,
+    pub accuracies: Vec<f64>,
+    pub best_k: usize,
+}
+
+fn default_n_jobs() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+// Навчання метрики; мітки класів (для HVDM) перетворюються в індекси
+fn fit_metric(distance: Distance, x: &[Vec<f64>], categorical: &[bool], y: Option<&[String]>) -> DistanceMetric {
+    let class_indices: Option<Vec<usize>> = match y {
+        Some(y) if distance.needs_labels() => {
+            let mut classes: Vec<&String> = y.iter().collect();
+            classes.sort();
+            classes.dedup();
+            Some(y.iter().map(|label| classes.binary_search(&label).unwrap()).collect())
+        }
+        _ => None,
+    };
+    DistanceMetric::fit(distance, x, categorical, class_indices.as_deref())
+}
+
+fn neighbor_weights(weights: Weights, neighbors: &[Neighbor]) -> Vec<f64> {
+    match weights {
+        Weights::Uniform => vec![1.0; neighbors.len()],
+        Weights::Distance => {
+            if neighbors.iter().any(|n| n.distance == 0.0) {
+                neighbors.iter().map(|n| if n.distance == 0.0 { 1.0 } else { 0.0 }).collect()
+            } else {
+                neighbors.iter().map(|n| 1.0 / n.distance).collect()
+            }
+        }
+        Weights::Gaussian(bandwidth) => neighbors.iter()
+            .map(|n| (-n.distance * n.distance / (2.0 * bandwidth * bandwidth)).exp())
+            .collect(),
+    }
+}
+
+fn class_votes<'a>(y_train: &'a [String], neighbors: &[Neighbor], weights: Weights) -> HashMap<&'a String, f64> {
+    let mut class_votes: HashMap<&String, f64> = HashMap::new();
+    for (neighbor, weight) in neighbors.iter().zip(neighbor_weights(weights, neighbors)) {
+        *class_votes.entry(&y_train[neighbor.index]).or_insert(0.0) += weight;
+    }
+    class_votes
+}
+
+// При рівній кількості голосів перемагає клас найближчого з сусідів; None - сусідів немає
+fn majority_vote(y_train: &[String], neighbors: &[Neighbor], weights: Weights) -> Option<String> {
+    let class_votes = class_votes(y_train, neighbors, weights);
+    let max_votes = class_votes.values().copied().fold(f64::NEG_INFINITY, f64::max);
+    neighbors.iter()
+        .map(|neighbor| &y_train[neighbor.index])
+        .find(|class| class_votes[class] == max_votes)
+        .cloned()
+}
+
+// Частки (зважених) голосів сусідів за кожен клас
+fn vote_shares(y_train: &[String], neighbors: &[Neighbor], weights: Weights) -> HashMap<String, f64> {
+    let class_votes = class_votes(y_train, neighbors, weights);
+    let total: f64 = class_votes.values().sum();
+    class_votes.into_iter()
+        .map(|(class, votes)| (class.clone(), if total > 0.0 { votes / total } else { 0.0 }))
+        .collect()
+}
+
+// Навчальні точки та мітки у текстовому вигляді: рядки "row ..." і "label ..."
+fn training_to_text(index: &NeighborIndex, y_train: &[String]) -> String {
+    let mut text = String::new();
+    for (i, label) in y_train.iter().enumerate() {
+        text.push_str(&values_line("row", index.point(i)));
+        text.push_str(&format!("\nlabel {label}\n"));
+    }
+    text
+}
+
+// (навчальні точки, мітки)
+type TrainingData = (Vec<Vec<f64>>, Vec<String>);
+
+fn training_from_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Result<TrainingData, Box<dyn Error>> {
+    let mut x = Vec::new();
+    let mut y = Vec::new();
+    for line in lines {
+        if let Some(label) = line.strip_prefix("label ") {
+            y.push(label.to_string());
+        } else {
+            x.push(parse_values_line(Some(line), "row")?);
+        }
+    }
+    if x.len() != y.len() {
+        return Err("кількість точок і міток не збігається".into());
+    }
+    Ok((x, y))
+}
+
+// Налаштування пошуку сусідів із заголовка збереженої моделі
+fn parse_search_config(distance: &str, weights: &str, algorithm: &str) -> Result<(Distance, Weights, Algorithm), Box<dyn Error>> {
+    Ok((
+        Distance::from_name(distance).ok_or(format!("невідома відстань {distance}"))?,
+        Weights::from_name(weights).ok_or(format!("невідомі ваги {weights}"))?,
+        Algorithm::from_name(algorithm).ok_or(format!("невідомий алгоритм {algorithm}"))?,
+    ))
+}
 
 pub struct KNNClassifier {
     k: usize,
-    x_train: Vec<Vec<f64>>,
+    algorithm: Algorithm,
+    distance: Distance,
+    weights: Weights,
+    n_jobs: usize,
+    index: NeighborIndex,
+    encoder: Option<MixedEncoder>,
     y_train: Vec<String>,
 }
 
@@ -10,43 +235,577 @@ impl KNNClassifier {
     pub fn new(k: usize) -> Self {
         KNNClassifier {
             k,
-            x_train: Vec::new(),
+            algorithm: Algorithm::Auto,
+            distance: Distance::Euclidean,
+            weights: Weights::Uniform,
+            n_jobs: default_n_jobs(),
+            index: NeighborIndex::build(&[], DistanceMetric::new(Distance::Euclidean), Algorithm::BruteForce),
+            encoder: None,
             y_train: Vec::new(),
         }
     }
 
-    pub fn fit(&mut self, x: &Vec<Vec<f64>>, y: &Vec<String>) {
-        // Зберегти навчальні дані у структурі
-        self.x_train = x.clone();
-        self.y_train = y.clone();
+    // Якщо алгоритм несумісний з метрикою, індекс замінює його (див. NeighborIndex::resolve);
+    // фактичний алгоритм після fit повертає algorithm()
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn with_distance(mut self, distance: Distance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    pub fn with_weights(mut self, weights: Weights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    // Кількість потоків для пакетного прогнозу (1 - без паралелізму)
+    pub fn with_n_jobs(mut self, n_jobs: usize) -> Self {
+        self.n_jobs = n_jobs;
+        self
+    }
+
+    pub fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        self.fit_encoded(x, &[], y);
+        self.encoder = None;
+    }
+
+    // Навчання на категоріальних/змішаних рядках (weather.csv, buy_computer.csv);
+    // має сенс з Hamming, HEOM або HVDM
+    pub fn fit_categorical(&mut self, x: &[Vec<String>], y: &[String]) {
+        let encoder = MixedEncoder::fit(x);
+        let encoded = encoder.transform(x);
+        self.fit_encoded(&encoded, &encoder.categorical, y);
+        self.encoder = Some(encoder);
     }
 
+    fn fit_encoded(&mut self, x: &[Vec<f64>], categorical: &[bool], y: &[String]) {
+        // Зберегти навчальні дані у структурі (індекс для пошуку сусідів)
+        let metric = fit_metric(self.distance, x, categorical, Some(y));
+        self.index = NeighborIndex::build(x, metric, self.algorithm);
+        self.y_train = y.to_vec();
+    }
+
+    // Алгоритм, який фактично використовується (для Auto - обраний за даними та метрикою)
+    pub fn algorithm(&self) -> Algorithm {
+        self.index.algorithm()
+    }
+
+    // Запити розподіляються між n_jobs потоками; індекс лише читається, тому спільний
     pub fn predict(&self, data: &[Vec<f64>]) -> Vec<String> {
-        let mut predictions = Vec::new();
-        for x in data {
-            predictions.push(self.predict_one(x));
+        parallel_map(data, self.n_jobs, |x| self.predict_one(x))
+    }
+
+    pub fn predict_categorical(&self, data: &[Vec<String>]) -> Vec<String> {
+        self.predict(&self.encode(data))
+    }
+
+    // Числове кодування рядків так само, як у fit_categorical (для predict_proba та kneighbors);
+    // якщо модель навчена через fit, усі ознаки числові (порожнє чи нечислове значення - NaN)
+    pub fn encode(&self, data: &[Vec<String>]) -> Vec<Vec<f64>> {
+        match &self.encoder {
+            Some(encoder) => encoder.transform(data),
+            None => data.iter()
+                .map(|row| row.iter().map(|value| value.parse::<f64>().unwrap_or(f64::NAN)).collect())
+                .collect(),
+        }
+    }
+
+    pub fn predict_one(&self, x: &[f64]) -> String {
+        let k_nearest = self.index.k_nearest(x, self.k);  // на випадок, якщо k > n, повернеться n сусідів
+
+        majority_vote(&self.y_train, &k_nearest, self.weights).unwrap_or_default()
+    }
+
+    // Частки (зважених) голосів k сусідів за кожен клас
+    pub fn predict_proba(&self, data: &[Vec<f64>]) -> Vec<HashMap<String, f64>> {
+        parallel_map(data, self.n_jobs, |x| {
+            vote_shares(&self.y_train, &self.index.k_nearest(x, self.k), self.weights)
+        })
+    }
+
+    // k найближчих навчальних прикладів (індекс у навчальній вибірці та відстань) - для пояснення прогнозу
+    pub fn kneighbors(&self, data: &[Vec<f64>]) -> Vec<Vec<Neighbor>> {
+        parallel_map(data, self.n_jobs, |x| self.index.k_nearest(x, self.k))
+    }
+
+    pub fn neighbor_label(&self, neighbor: &Neighbor) -> &String {
+        &self.y_train[neighbor.index]
+    }
+
+    // Підбір k перехресною перевіркою (folds = None - leave-one-out, інакше стратифіковані частини).
+    // Для кожної точки сусіди шукаються один раз (до max k), а голоси для всіх k рахуються по префіксах цього списку.
+    // Для моделі з fit_categorical x - закодовані рядки (encode), і метрика враховує ті самі категоріальні ознаки.
+    pub fn select_k(&self, x: &[Vec<f64>], y: &[String], k_range: RangeInclusive<usize>, folds: Option<usize>, seed: u64) -> KSelection {
+        let k_values: Vec<usize> = k_range.filter(|&k| k > 0).collect();
+        let k_max = k_values.iter().copied().max().unwrap_or(1);
+        let mut correct = vec![0_usize; k_values.len()];
+
+        let n = y.len();
+        let categorical: &[bool] = self.encoder.as_ref().map(|encoder| encoder.categorical.as_slice()).unwrap_or_default();
+
+        let mut evaluate = |model: &KNNClassifier, neighbors: &[Neighbor], label: &String| {
+            for (predicted, hits) in model.predict_prefixes(neighbors, &k_values).iter().zip(correct.iter_mut()) {
+                if predicted == label {
+                    *hits += 1;
+                }
+            }
+        };
+
+        match folds {
+            None => {
+                let mut model = self.with_same_config(k_max);
+                model.fit_encoded(x, categorical, y);
+                for (i, point) in x.iter().enumerate() {
+                    // шукаємо на одного сусіда більше і викидаємо саму точку
+                    let mut neighbors = model.index.k_nearest(point, k_max + 1);
+                    match neighbors.iter().position(|neighbor| neighbor.index == i) {
+                        Some(position) => { neighbors.remove(position); }
+                        None => { neighbors.pop(); }
+                    }
+                    evaluate(&model, &neighbors, &y[i]);
+                }
+            }
+            Some(folds) => {
+                for (train, test) in stratified_k_fold(y, folds, seed) {
+                    let mut model = self.with_same_config(k_max);
+                    model.fit_encoded(&select_rows(x, &train), categorical, &select_rows(y, &train));
+                    for &i in &test {
+                        let neighbors = model.index.k_nearest(&x[i], k_max);
+                        evaluate(&model, &neighbors, &y[i]);
+                    }
+                }
+            }
+        }
+
+        let accuracies: Vec<f64> = correct.iter().map(|&c| c as f64 / n.max(1) as f64).collect();
+        // при рівній точності - менше k
+        let best_k = k_values.iter()
+            .zip(accuracies.iter())
+            .fold((k_values.first().copied().unwrap_or(1), f64::NEG_INFINITY), |best, (&k, &acc)| if acc > best.1 { (k, acc) } else { best })
+            .0;
+        KSelection { k_values, accuracies, best_k }
+    }
+
+    fn with_same_config(&self, k: usize) -> KNNClassifier {
+        KNNClassifier::new(k)
+            .with_algorithm(self.algorithm)
+            .with_distance(self.distance)
+            .with_weights(self.weights)
+            .with_n_jobs(self.n_jobs)
+    }
+
+    // Прогнози для кожного k з k_values за одним відсортованим списком сусідів
+    fn predict_prefixes(&self, neighbors: &[Neighbor], k_values: &[usize]) -> Vec<String> {
+        let mut votes: HashMap<&String, f64> = HashMap::new();
+        // для Weights::Distance: голоси сусідів на нульовій відстані рахуються окремо і мають пріоритет
+        let mut zero_votes: HashMap<&String, f64> = HashMap::new();
+        let mut prefix_predictions: Vec<String> = Vec::with_capacity(neighbors.len());
+        for (position, neighbor) in neighbors.iter().enumerate() {
+            let class = &self.y_train[neighbor.index];
+            match self.weights {
+                Weights::Distance if neighbor.distance == 0.0 => *zero_votes.entry(class).or_insert(0.0) += 1.0,
+                _ => *votes.entry(class).or_insert(0.0) += neighbor_weights(self.weights, std::slice::from_ref(neighbor))[0],
+            }
+            let current = if zero_votes.is_empty() { &votes } else { &zero_votes };
+            let max_votes = current.values().copied().fold(f64::NEG_INFINITY, f64::max);
+            let winner = neighbors[..=position].iter()
+                .map(|n| &self.y_train[n.index])
+                .find(|c| current.get(c).copied() == Some(max_votes))
+                .cloned()
+                .unwrap_or_default();
+            prefix_predictions.push(winner);
+        }
+        k_values.iter()
+            .map(|&k| prefix_predictions.get(k.min(neighbors.len()).saturating_sub(1)).cloned().unwrap_or_default())
+            .collect()
+    }
+}
+
+// Зберігаються параметри, кодування категорій (для fit_categorical) та навчальні дані;
+// метрика та індекс будуються заново при завантаженні
+impl Classifier for KNNClassifier {
+    fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        self.fit_encoded(x, &[], y);
+        self.encoder = None;
+    }
+
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        KNNClassifier::predict(self, x)
+    }
+
+    fn to_text(&self) -> String {
+        let encoder = self.encoder.as_ref().map(MixedEncoder::to_text).unwrap_or_default();
+        format!("knn {} {:?} {:?} {:?} {}\n{}{}", self.k, self.distance, self.weights, self.algorithm, self.n_jobs,
+                encoder, training_to_text(&self.index, &self.y_train))
+    }
+}
+
+impl KNNClassifier {
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let mut model = match header.as_slice() {
+            ["knn", k, distance, weights, algorithm, n_jobs] => {
+                let (distance, weights, algorithm) = parse_search_config(distance, weights, algorithm)?;
+                KNNClassifier::new(k.parse()?)
+                    .with_distance(distance)
+                    .with_weights(weights)
+                    .with_algorithm(algorithm)
+                    .with_n_jobs(n_jobs.parse()?)
+            }
+            _ => return Err(format!("невідомий заголовок kNN: {:?}", header).into()),
+        };
+        let (encoder_lines, training_lines): (Vec<&str>, Vec<&str>) = lines
+            .partition(|line| line.starts_with("categorical") || line.starts_with("code "));
+        let (x, y) = training_from_lines(training_lines.into_iter())?;
+        match MixedEncoder::from_lines(&encoder_lines)? {
+            Some(encoder) => {
+                model.fit_encoded(&x, &encoder.categorical, &y);
+                model.encoder = Some(encoder);
+            }
+            None => Classifier::fit(&mut model, &x, &y),
+        }
+        Ok(model)
+    }
+}
+
+// Регресія k найближчих сусідів: (зважене) середнє цільових значень сусідів
+pub struct KNNRegressor {
+    k: usize,
+    algorithm: Algorithm,
+    distance: Distance,
+    weights: Weights,
+    n_jobs: usize,
+    index: NeighborIndex,
+    y_train: Vec<f64>,
+}
+
+impl KNNRegressor {
+    pub fn new(k: usize) -> Self {
+        KNNRegressor {
+            k,
+            algorithm: Algorithm::Auto,
+            distance: Distance::Euclidean,
+            weights: Weights::Uniform,
+            n_jobs: default_n_jobs(),
+            index: NeighborIndex::build(&[], DistanceMetric::new(Distance::Euclidean), Algorithm::BruteForce),
+            y_train: Vec::new(),
         }
-        predictions
     }
 
-    pub fn predict_one(&self, x: &Vec<f64>) -> String {
-        let mut distances: Vec<(f64, &String)> = Vec::with_capacity(self.x_train.len());
-        for (xi, yi) in self.x_train.iter().zip(self.y_train.iter()) {
-            let mut dist_sq = 0.0;
-            for (a, b) in xi.iter().zip(x.iter()) {
-                let diff = a - b;
-                dist_sq += diff * diff;
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    // HVDM потребує класів, тому для регресії замість неї використовується HEOM
+    // (та сама нормована відстань для числових ознак, категорії - на рівність; див. DistanceMetric::fit)
+    pub fn with_distance(mut self, distance: Distance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    pub fn with_weights(mut self, weights: Weights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    pub fn with_n_jobs(mut self, n_jobs: usize) -> Self {
+        self.n_jobs = n_jobs;
+        self
+    }
+
+    pub fn fit(&mut self, x: &[Vec<f64>], y: &[f64]) {
+        let metric = fit_metric(self.distance, x, &[], None);
+        self.index = NeighborIndex::build(x, metric, self.algorithm);
+        self.y_train = y.to_vec();
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.index.algorithm()
+    }
+
+    pub fn predict(&self, data: &[Vec<f64>]) -> Vec<f64> {
+        parallel_map(data, self.n_jobs, |x| self.predict_one(x))
+    }
+
+    pub fn predict_one(&self, x: &[f64]) -> f64 {
+        let neighbors = self.index.k_nearest(x, self.k);
+        let mut weights = neighbor_weights(self.weights, &neighbors);
+        // гаусові ваги далеких сусідів можуть усі обнулитися - тоді звичайне середнє
+        if weights.iter().sum::<f64>() <= 0.0 {
+            weights = vec![1.0; neighbors.len()];
+        }
+        let total_weight: f64 = weights.iter().sum();
+        let weighted_sum: f64 = neighbors.iter()
+            .zip(weights.iter())
+            .map(|(neighbor, w)| w * self.y_train[neighbor.index])
+            .sum();
+        if total_weight > 0.0 { weighted_sum / total_weight } else { f64::NAN }
+    }
+
+    pub fn kneighbors(&self, data: &[Vec<f64>]) -> Vec<Vec<Neighbor>> {
+        parallel_map(data, self.n_jobs, |x| self.index.k_nearest(x, self.k))
+    }
+
+    // Коефіцієнт детермінації R^2 на (x, y)
+    pub fn score(&self, x: &[Vec<f64>], y: &[f64]) -> f64 {
+        let predictions = self.predict(x);
+        let mean = y.iter().sum::<f64>() / y.len().max(1) as f64;
+        let ss_res: f64 = y.iter().zip(predictions.iter()).map(|(t, p)| (t - p).powi(2)).sum();
+        let ss_tot: f64 = y.iter().map(|t| (t - mean).powi(2)).sum();
+        if ss_tot > 0.0 { 1.0 - ss_res / ss_tot } else { 0.0 }
+    }
+}
+
+// Класифікація за всіма навчальними прикладами в радіусі radius;
+// якщо в радіусі нікого немає - повертається outlier_label
+pub struct RadiusNeighborsClassifier {
+    radius: f64,
+    outlier_label: String,
+    algorithm: Algorithm,
+    distance: Distance,
+    weights: Weights,
+    n_jobs: usize,
+    index: NeighborIndex,
+    y_train: Vec<String>,
+}
+
+impl RadiusNeighborsClassifier {
+    pub fn new(radius: f64, outlier_label: &str) -> Self {
+        RadiusNeighborsClassifier {
+            radius,
+            outlier_label: outlier_label.to_string(),
+            algorithm: Algorithm::Auto,
+            distance: Distance::Euclidean,
+            weights: Weights::Uniform,
+            n_jobs: default_n_jobs(),
+            index: NeighborIndex::build(&[], DistanceMetric::new(Distance::Euclidean), Algorithm::BruteForce),
+            y_train: Vec::new(),
+        }
+    }
+
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn with_distance(mut self, distance: Distance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    pub fn with_weights(mut self, weights: Weights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    pub fn with_n_jobs(mut self, n_jobs: usize) -> Self {
+        self.n_jobs = n_jobs;
+        self
+    }
+
+    pub fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        let metric = fit_metric(self.distance, x, &[], Some(y));
+        self.index = NeighborIndex::build(x, metric, self.algorithm);
+        self.y_train = y.to_vec();
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.index.algorithm()
+    }
+
+    pub fn predict(&self, data: &[Vec<f64>]) -> Vec<String> {
+        parallel_map(data, self.n_jobs, |x| {
+            let neighbors = self.index.within_radius(x, self.radius);
+            majority_vote(&self.y_train, &neighbors, self.weights).unwrap_or_else(|| self.outlier_label.clone())
+        })
+    }
+
+    // Для викидів (жодного сусіда в радіусі) - порожній розподіл
+    pub fn predict_proba(&self, data: &[Vec<f64>]) -> Vec<HashMap<String, f64>> {
+        parallel_map(data, self.n_jobs, |x| {
+            vote_shares(&self.y_train, &self.index.within_radius(x, self.radius), self.weights)
+        })
+    }
+
+    // Усі навчальні приклади в радіусі, від найближчого
+    pub fn radius_neighbors(&self, data: &[Vec<f64>]) -> Vec<Vec<Neighbor>> {
+        parallel_map(data, self.n_jobs, |x| self.index.within_radius(x, self.radius))
+    }
+
+    pub fn neighbor_label(&self, neighbor: &Neighbor) -> &String {
+        &self.y_train[neighbor.index]
+    }
+}
+
+impl Classifier for RadiusNeighborsClassifier {
+    fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        RadiusNeighborsClassifier::fit(self, x, y);
+    }
+
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        RadiusNeighborsClassifier::predict(self, x)
+    }
+
+    fn to_text(&self) -> String {
+        format!("radius_neighbors {} {:?} {:?} {:?} {}\noutlier {}\n{}", self.radius, self.distance, self.weights, self.algorithm,
+                self.n_jobs, self.outlier_label, training_to_text(&self.index, &self.y_train))
+    }
+}
+
+impl RadiusNeighborsClassifier {
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let outlier_label = lines.next().and_then(|line| line.strip_prefix("outlier ")).ok_or("немає рядка outlier")?;
+        let mut model = match header.as_slice() {
+            ["radius_neighbors", radius, distance, weights, algorithm, n_jobs] => {
+                let (distance, weights, algorithm) = parse_search_config(distance, weights, algorithm)?;
+                RadiusNeighborsClassifier::new(radius.parse()?, outlier_label)
+                    .with_distance(distance)
+                    .with_weights(weights)
+                    .with_algorithm(algorithm)
+                    .with_n_jobs(n_jobs.parse()?)
             }
-            let dist = dist_sq.sqrt();
-            distances.push((dist, yi));
+            _ => return Err(format!("невідомий заголовок radius neighbours: {:?}", header).into()),
+        };
+        let (x, y) = training_from_lines(lines)?;
+        RadiusNeighborsClassifier::fit(&mut model, &x, &y);
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(values: &str) -> Vec<String> {
+        values.split_whitespace().map(|label| label.to_string()).collect()
+    }
+
+    #[test]
+    fn weighting_lets_the_close_neighbour_win() {
+        let x = vec![vec![0.0], vec![1.0], vec![5.0]];
+        let y = labels("a a b");
+        let query = vec![vec![4.0]];
+
+        let mut uniform = KNNClassifier::new(3);
+        uniform.fit(&x, &y);
+        assert_eq!(uniform.predict(&query), labels("a"));
+        assert!((uniform.predict_proba(&query)[0]["a"] - 2.0 / 3.0).abs() < 1e-12);
+        let neighbors = &uniform.kneighbors(&query)[0];
+        assert_eq!(neighbors.iter().map(|n| n.index).collect::<Vec<_>>(), vec![2, 1, 0]);
+        assert_eq!(neighbors.iter().map(|n| n.distance).collect::<Vec<_>>(), vec![1.0, 3.0, 4.0]);
+        assert_eq!(uniform.neighbor_label(&neighbors[0]), "b");
+
+        for weights in [Weights::Distance, Weights::Gaussian(1.0)] {
+            let mut weighted = KNNClassifier::new(3).with_weights(weights);
+            weighted.fit(&x, &y);
+            assert_eq!(weighted.predict(&query), labels("b"), "{weights:?}");
+            let proba = &weighted.predict_proba(&query)[0];
+            assert!((proba.values().sum::<f64>() - 1.0).abs() < 1e-12);
         }
-        distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
-        let k_nearest = &distances[..self.k.min(distances.len())];  // на випадок, якщо k > n
 
-        let mut class_votes: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-        for &(_, class) in k_nearest {
-            *class_votes.entry(class.clone()).or_insert(0) += 1;
+        // сусід на нульовій відстані забирає всі голоси
+        let mut exact = KNNClassifier::new(3).with_weights(Weights::Distance);
+        exact.fit(&x, &y);
+        assert_eq!(exact.predict_proba(&[vec![5.0]])[0]["b"], 1.0);
+    }
+
+    #[test]
+    fn select_k_keeps_categorical_features() {
+        // з категоріальною першою ознакою найближчий сусід завжди з тієї самої категорії;
+        // якби коди категорій порівнювалися як числа, "a" і "b" були б ближчими за дві "a"
+        let x: Vec<Vec<String>> = ["a 0", "a 6", "b 0", "b 6", "c 4", "c 10"].iter()
+            .map(|row| row.split_whitespace().map(|value| value.to_string()).collect())
+            .collect();
+        let y = labels("x x y y x x");
+        let mut model = KNNClassifier::new(1).with_distance(Distance::Heom);
+        model.fit_categorical(&x, &y);
+        let selection = model.select_k(&model.encode(&x), &y, 1..=1, None, 0);
+        assert_eq!(selection.accuracies, vec![1.0]);
+    }
+
+    #[test]
+    fn regression_and_radius_neighbours() {
+        let x = vec![vec![0.0], vec![1.0], vec![2.0], vec![10.0]];
+        let mut regressor = KNNRegressor::new(2);
+        regressor.fit(&x, &[1.0, 3.0, 5.0, 100.0]);
+        assert_eq!(regressor.predict(&[vec![0.4], vec![1.6]]), vec![2.0, 4.0]);
+        // HVDM без класів замінюється на HEOM
+        let mut hvdm = KNNRegressor::new(2).with_distance(Distance::Hvdm);
+        hvdm.fit(&x, &[1.0, 3.0, 5.0, 100.0]);
+        assert_eq!(hvdm.predict(&[vec![0.4]]), vec![2.0]);
+
+        let mut radius = RadiusNeighborsClassifier::new(1.5, "outlier");
+        radius.fit(&x, &labels("a a b b"));
+        assert_eq!(radius.predict(&[vec![0.2], vec![10.5], vec![6.0]]), labels("a b outlier"));
+    }
+
+    #[test]
+    fn encode_without_encoder_parses_numbers() {
+        let mut model = KNNClassifier::new(1);
+        model.fit(&[vec![0.0, 1.0], vec![5.0, 5.0]], &labels("a b"));
+        let rows = vec![vec!["4.5".to_string(), "".to_string()]];
+        let encoded = model.encode(&rows);
+        assert_eq!(encoded[0][0], 4.5);
+        assert!(encoded[0][1].is_nan());
+    }
+
+    #[test]
+    fn select_k_folds_are_stratified() {
+        // по три точки на клас: у кожній з трьох частин кожен клас має рівно одну тестову точку
+        let x: Vec<Vec<f64>> = (0..9).map(|i| vec![(i / 3) as f64 * 10.0 + (i % 3) as f64]).collect();
+        let y = labels("a a a b b b c c c");
+        for seed in 0..5 {
+            let selection = KNNClassifier::new(1).select_k(&x, &y, 1..=2, Some(3), seed);
+            assert_eq!(selection.accuracies, vec![1.0, 1.0]);
+        }
+    }
+
+    fn weather() -> (Vec<Vec<String>>, Vec<String>) {
+        let mut reader = csv::Reader::from_path("datasets/weather.csv").unwrap();
+        let mut x = Vec::new();
+        let mut y = Vec::new();
+        for record in reader.records() {
+            let record: Vec<String> = record.unwrap().iter().map(|s| s.to_string()).collect();
+            let (label, features) = record.split_last().unwrap();
+            x.push(features.to_vec());
+            y.push(label.clone());
+        }
+        (x, y)
+    }
+
+    // Збережена модель з fit_categorical відновлює кодування категорій і n_jobs
+    #[test]
+    fn categorical_model_round_trip() {
+        let (x, y) = weather();
+        for distance in [Distance::Hamming, Distance::Heom, Distance::Hvdm] {
+            let mut model = KNNClassifier::new(3).with_distance(distance).with_n_jobs(2);
+            model.fit_categorical(&x, &y);
+            let text = Classifier::to_text(&model);
+            let restored = KNNClassifier::from_text(&text).unwrap();
+            assert_eq!(restored.n_jobs, 2);
+            assert_eq!(Classifier::to_text(&restored), text);
+            assert_eq!(restored.predict_categorical(&x), model.predict_categorical(&x));
         }
-        class_votes.into_iter().max_by_key(|entry| entry.1).unwrap().0
+    }
+
+    #[test]
+    fn numeric_model_encodes_strings_as_numbers() {
+        let x = vec![vec![0.0, 0.0], vec![0.1, 0.2], vec![5.0, 5.0], vec![5.2, 4.9]];
+        let y: Vec<String> = ["a", "a", "b", "b"].iter().map(|s| s.to_string()).collect();
+        let mut model = KNNClassifier::new(1);
+        model.fit(&x, &y);
+        let query = vec![vec!["4.8".to_string(), "5.1".to_string()]];
+        assert_eq!(model.predict_categorical(&query), vec!["b"]);
+        let restored = KNNClassifier::from_text(&Classifier::to_text(&model)).unwrap();
+        assert!(restored.encoder.is_none());
+        assert_eq!(restored.predict(&x), y);
     }
 }
@@ -0,0 +1,28 @@
+// Алгоритми та утиліти; демо-виклики - у main.rs
+pub mod util;
+pub mod one_r;
+pub mod naive_bayes;
+pub mod decision_tree;
+pub mod knn;
+pub mod importance;
+pub mod regression_tree;
+pub mod random_forest;
+pub mod adaboost;
+pub mod gradient_boosting;
+pub mod neighbors;
+pub mod distance;
+pub mod preprocessing;
+pub mod imputer;
+pub mod outliers;
+pub mod pipeline;
+pub mod pca;
+pub mod discretize;
+pub mod dummy;
+pub mod decision_list;
+pub mod prism;
+pub mod ripper;
+pub mod optimize;
+pub mod logistic;
+pub mod svm;
+pub mod mlp;
+pub mod online;
@@ -0,0 +1,329 @@
+use std::error::Error;
+use crate::optimize::{lbfgs, proximal_gradient_descent};
+use crate::pipeline::Classifier;
+use crate::preprocessing::{parse_values_line, values_line};
+use crate::util::{dot, softmax};
+
+// Регуляризація ваг (вільний член не штрафується); сила - коефіцієнт при штрафі в середній функції втрат
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Penalty {
+    None,
+    // lambda * sum |w|
+    L1(f64),
+    // lambda / 2 * sum w^2
+    L2(f64),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Solver {
+    // (проксимальний) градієнтний спуск з пошуком кроку
+    GradientDescent,
+    // L-BFGS; негладкий штраф L1 він не підтримує, тому з L1 завжди використовується градієнтний спуск
+    Lbfgs,
+}
+
+// Логістична регресія: для двох класів - сигмоїда з одним вектором ваг (для другого з класів),
+// для більшої кількості - мультиноміальна (softmax) з вектором ваг для кожного класу.
+// Ознаки бажано масштабувати (Pipeline зі Scaler), інакше оптимізація сходиться повільно.
+pub struct LogisticRegression {
+    penalty: Penalty,
+    solver: Solver,
+    max_iter: usize,
+    learning_rate: f64,
+    tolerance: f64,
+    classes: Vec<String>,
+    coefficients: Vec<Vec<f64>>,  // coefficients[рядок][ознака]; один рядок для двох класів
+    intercepts: Vec<f64>,
+    n_iter: usize,
+}
+
+// Стійкий до переповнення ln(1 + e^z)
+fn softplus(z: f64) -> f64 {
+    if z > 0.0 { z + (-z).exp().ln_1p() } else { z.exp().ln_1p() }
+}
+
+fn sigmoid(z: f64) -> f64 {
+    if z >= 0.0 { 1.0 / (1.0 + (-z).exp()) } else { z.exp() / (1.0 + z.exp()) }
+}
+
+impl Default for LogisticRegression {
+    fn default() -> Self {
+        LogisticRegression::new()
+    }
+}
+
+impl LogisticRegression {
+    pub fn new() -> Self {
+        LogisticRegression {
+            penalty: Penalty::L2(0.01),
+            solver: Solver::Lbfgs,
+            max_iter: 500,
+            learning_rate: 1.0,
+            tolerance: 1e-6,
+            classes: Vec::new(),
+            coefficients: Vec::new(),
+            intercepts: Vec::new(),
+            n_iter: 0,
+        }
+    }
+
+    pub fn with_penalty(mut self, penalty: Penalty) -> Self {
+        self.penalty = penalty;
+        self
+    }
+
+    pub fn with_solver(mut self, solver: Solver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    // Початковий крок градієнтного спуску (далі він зменшується, якщо функція втрат не спадає)
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    // Середня логістична втрата (плюс штраф L2) та її градієнт; параметри - рядки ваг, останній елемент рядка - вільний член
+    fn loss_and_gradient(&self, params: &[f64], x: &[Vec<f64>], targets: &[usize], rows: usize) -> (f64, Vec<f64>) {
+        let width = params.len() / rows;
+        let n = x.len().max(1) as f64;
+        let mut loss = 0.0;
+        let mut gradient = vec![0.0; params.len()];
+        let mut scores = vec![0.0; rows];
+        for (sample, &target) in x.iter().zip(targets) {
+            for (r, score) in scores.iter_mut().enumerate() {
+                let weights = &params[r * width..(r + 1) * width];
+                *score = weights[width - 1] + dot(sample, weights);
+            }
+            // похибки (ймовірність - ціль) для кожного рядка ваг
+            let errors: Vec<f64> = if rows == 1 {
+                let z = scores[0];
+                let positive = (target == 1) as usize as f64;
+                loss += softplus(z) - positive * z;
+                vec![sigmoid(z) - positive]
+            } else {
+                let log_normalizer = {
+                    let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                    max + scores.iter().map(|s| (s - max).exp()).sum::<f64>().ln()
+                };
+                loss += log_normalizer - scores[target];
+                softmax(&mut scores);
+                scores.iter().enumerate().map(|(r, p)| p - (r == target) as usize as f64).collect()
+            };
+            for (r, error) in errors.iter().enumerate() {
+                let row_gradient = &mut gradient[r * width..(r + 1) * width];
+                row_gradient.iter_mut().zip(sample).for_each(|(g, v)| *g += error * v);
+                row_gradient[width - 1] += error;
+            }
+        }
+        loss /= n;
+        gradient.iter_mut().for_each(|g| *g /= n);
+        if let Penalty::L2(lambda) = self.penalty {
+            for (i, (w, g)) in params.iter().zip(gradient.iter_mut()).enumerate() {
+                if i % width != width - 1 {
+                    loss += 0.5 * lambda * w * w;
+                    *g += lambda * w;
+                }
+            }
+        }
+        (loss, gradient)
+    }
+
+    pub fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        let mut classes: Vec<String> = y.to_vec();
+        classes.sort();
+        classes.dedup();
+        let targets: Vec<usize> = y.iter().map(|label| classes.binary_search(label).unwrap()).collect();
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        let rows = if classes.len() <= 2 { 1 } else { classes.len() };
+        let width = dims + 1;
+
+        let objective = |params: &[f64]| self.loss_and_gradient(params, x, &targets, rows);
+        let initial = vec![0.0; rows * width];
+        let (params, n_iter) = match (self.penalty, self.solver) {
+            (Penalty::L1(lambda), _) => {
+                // м'яке порогування ваг (вільні члени без змін)
+                let soft_threshold = |params: &mut [f64], step: f64| {
+                    for (i, w) in params.iter_mut().enumerate() {
+                        if i % width != width - 1 {
+                            *w = w.signum() * (w.abs() - step * lambda).max(0.0);
+                        }
+                    }
+                };
+                proximal_gradient_descent(objective, soft_threshold, initial, self.learning_rate, self.max_iter, self.tolerance)
+            }
+            (_, Solver::GradientDescent) => proximal_gradient_descent(objective, |_: &mut [f64], _| {}, initial, self.learning_rate, self.max_iter, self.tolerance),
+            (_, Solver::Lbfgs) => lbfgs(objective, initial, 10, self.max_iter, self.tolerance),
+        };
+
+        self.coefficients = params.chunks(width).map(|row| row[..dims].to_vec()).collect();
+        self.intercepts = params.chunks(width).map(|row| row[dims]).collect();
+        self.classes = classes;
+        self.n_iter = n_iter;
+    }
+
+    // Лінійні оцінки w·x + b для кожного рядка ваг
+    pub fn decision_function(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        x.iter()
+            .map(|row| {
+                self.coefficients.iter()
+                    .zip(self.intercepts.iter())
+                    .map(|(weights, b)| b + dot(row, weights))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Ймовірності класів у порядку classes()
+    pub fn predict_proba(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        self.decision_function(x)
+            .into_iter()
+            .map(|mut scores| {
+                if self.coefficients.len() == 1 {
+                    let p = sigmoid(scores[0]);
+                    if self.classes.len() == 2 { vec![1.0 - p, p] } else { vec![1.0] }
+                } else {
+                    softmax(&mut scores);
+                    scores
+                }
+            })
+            .collect()
+    }
+
+    pub fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        self.predict_proba(x)
+            .into_iter()
+            .map(|probabilities| {
+                let best = (0..probabilities.len()).fold(0, |best, c| if probabilities[c] > probabilities[best] { c } else { best });
+                self.classes[best].clone()
+            })
+            .collect()
+    }
+
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    // Ваги ознак: для двох класів - один рядок (для classes()[1]), інакше рядок для кожного класу
+    pub fn coefficients(&self) -> &[Vec<f64>] {
+        &self.coefficients
+    }
+
+    pub fn intercepts(&self) -> &[f64] {
+        &self.intercepts
+    }
+
+    // Кількість ітерацій оптимізатора під час останнього fit
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines().peekable();
+        if lines.next() != Some("logistic_regression") {
+            return Err("очікувався заголовок logistic_regression".into());
+        }
+        let mut model = LogisticRegression::new();
+        while let Some(class) = lines.peek().and_then(|line| line.strip_prefix("class ")) {
+            model.classes.push(class.to_string());
+            lines.next();
+        }
+        model.intercepts = parse_values_line(lines.next(), "intercept")?;
+        model.coefficients = lines.map(|line| parse_values_line(Some(line), "coef")).collect::<Result<_, _>>()?;
+        if model.coefficients.len() != model.intercepts.len() {
+            return Err("кількість рядків coef не збігається з intercept".into());
+        }
+        Ok(model)
+    }
+}
+
+impl Classifier for LogisticRegression {
+    fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        LogisticRegression::fit(self, x, y);
+    }
+
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        LogisticRegression::predict(self, x)
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = String::from("logistic_regression\n");
+        for class in &self.classes {
+            text.push_str(&format!("class {class}\n"));
+        }
+        text.push_str(&values_line("intercept", &self.intercepts));
+        text.push('\n');
+        for row in &self.coefficients {
+            text.push_str(&values_line("coef", row));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::load_classifier;
+    use crate::util::load_numeric_dataset;
+
+    fn toy_set() -> (Vec<Vec<f64>>, Vec<String>) {
+        let x = vec![
+            vec![-2.0, -1.0], vec![-1.0, -2.0], vec![-1.5, -1.5], vec![-1.0, -0.5],
+            vec![2.0, 1.0], vec![1.0, 2.0], vec![1.5, 1.5], vec![0.5, 1.0],
+        ];
+        let y = ["a", "a", "a", "a", "b", "b", "b", "b"].iter().map(|s| s.to_string()).collect();
+        (x, y)
+    }
+
+    // Лінійно роздільна вибірка: L-BFGS сходиться до того самого мінімуму, що й градієнтний спуск
+    #[test]
+    fn lbfgs_converges_on_separable_set() {
+        let (x, y) = toy_set();
+        let mut lbfgs_model = LogisticRegression::new().with_penalty(Penalty::L2(0.1)).with_max_iter(200).with_tolerance(1e-12);
+        lbfgs_model.fit(&x, &y);
+        assert!(lbfgs_model.n_iter() < 200);
+        assert_eq!(lbfgs_model.predict(&x), y);
+
+        let params: Vec<f64> = lbfgs_model.coefficients()[0].iter().copied().chain(lbfgs_model.intercepts().iter().copied()).collect();
+        let targets: Vec<usize> = y.iter().map(|label| (label == "b") as usize).collect();
+        let (_, gradient) = lbfgs_model.loss_and_gradient(&params, &x, &targets, 1);
+        assert!(gradient.iter().all(|g| g.abs() < 1e-6), "{gradient:?}");
+
+        let mut gd_model = LogisticRegression::new()
+            .with_penalty(Penalty::L2(0.1))
+            .with_solver(Solver::GradientDescent)
+            .with_max_iter(20000)
+            .with_tolerance(1e-12);
+        gd_model.fit(&x, &y);
+        for (a, b) in lbfgs_model.coefficients()[0].iter().zip(&gd_model.coefficients()[0]) {
+            assert!((a - b).abs() < 1e-4, "{a} != {b}");
+        }
+        assert!((lbfgs_model.intercepts()[0] - gd_model.intercepts()[0]).abs() < 1e-4);
+    }
+
+    #[test]
+    fn round_trip() {
+        let (x, y, _) = load_numeric_dataset("datasets/iris.csv", 1).unwrap();
+        let (toy_x, toy_y) = toy_set();
+        for (x, y, penalty) in [(&x, &y, Penalty::L2(0.01)), (&x, &y, Penalty::L1(0.01)), (&toy_x, &toy_y, Penalty::None)] {
+            let mut model = LogisticRegression::new().with_penalty(penalty);
+            model.fit(x, y);
+            let text = Classifier::to_text(&model);
+            let restored = LogisticRegression::from_text(&text).unwrap();
+            assert_eq!(restored.predict_proba(x), model.predict_proba(x));
+            assert_eq!(Classifier::to_text(&restored), text);
+            assert_eq!(load_classifier(&text).unwrap().predict(x), model.predict(x));
+        }
+    }
+}
@@ -1,11 +1,6 @@
 use std::error::Error;
 use csv::Reader;
-
-mod util;
-mod one_r;
-mod naive_bayes;
-mod decision_tree;
-mod knn;
+use data_mining_laba_1::{util, one_r, naive_bayes, decision_tree, knn, importance, regression_tree, random_forest, adaboost, gradient_boosting, neighbors, distance, preprocessing, imputer, outliers, pipeline, pca, discretize, dummy, prism, ripper, logistic, svm, mlp, online};
 
 fn one_r_test_call() -> Result<(), Box<dyn Error>> {
     let mut x: Vec<Vec<String>> = Vec::new();
@@ -32,6 +27,13 @@ fn one_r_test_call() -> Result<(), Box<dyn Error>> {
     let best_feature = model.get_best_feature_index();
 
     println!("Best Feature is: {}", feature_names[best_feature]);
+    model.print_error_table(&feature_names);
+    model.print_rules(&feature_names);
+
+    // Правила іншої ознаки для порівняння
+    let mut forced = one_r::OneRClassifier::new().with_forced_feature((best_feature + 1) % feature_names.len());
+    forced.fit(&x, &y);
+    forced.print_rules(&feature_names);
 
     let new_examples = vec![
         vec!["Sunny".to_string(), "Cool".to_string(), "Yes".to_string()],
@@ -53,7 +55,6 @@ fn naive_bayes_test_call() -> Result<(), Box<dyn Error>> {
     let mut reader = Reader::from_path("datasets/spam.csv")?;
     for record in reader.records() {
         let record = record?;
-        let n = record.len();
         let class_value = record.get(0_usize).unwrap().to_string();
         let features = record.iter().skip(1_usize)
                                           .map(|s| s.to_string())
@@ -73,26 +74,20 @@ fn naive_bayes_test_call() -> Result<(), Box<dyn Error>> {
 }
 
 fn knn_test_call() -> Result<(), Box<dyn Error>> {
-    let mut x_points: Vec<Vec<f64>> = Vec::new();
-    let mut y_points: Vec<String> = Vec::new();
-    let mut reader = Reader::from_path("datasets/iris.csv")?;
-    for result in reader.records() {
-        let record = result?;
-        let num_fields = record.len();
-    
-        let class_val = record.get(num_fields - 1).unwrap().to_string();
-    
-        let features: Vec<f64> = record.iter()
-            .skip(1) // skip the id
-            .take_while(|val| val.parse::<f64>().is_ok())
-            .map(|val| val.parse::<f64>().unwrap())
-            .collect();
-    
-        x_points.push(features);
-        y_points.push(class_val);
+    use preprocessing::Transformer;
+
+    // Порожні/нечислові значення стають пропусками (а не обрізають рядок) і заповнюються медіаною
+    let (x_raw, y_points, _) = util::load_numeric_dataset_with_missing("datasets/iris.csv", 1)?;
+    let x_points = imputer::SimpleImputer::new(imputer::Strategy::Median).fit_transform(&x_raw);
+
+    // Підбір k за leave-one-out замість фіксованого значення
+    let selection = knn::KNNClassifier::new(1).select_k(&x_points, &y_points, 1..=25, None, 3);
+    for (k, accuracy) in selection.k_values.iter().zip(selection.accuracies.iter()) {
+        println!("k = {k:>2}: LOO accuracy {accuracy:.4}");
     }
+    println!("Best k: {}", selection.best_k);
 
-    let mut knn_model = knn::KNNClassifier::new(3);
+    let mut knn_model = knn::KNNClassifier::new(selection.best_k);
     knn_model.fit(&x_points, &y_points);
 
     // Тестові точки
@@ -134,9 +129,936 @@ fn decision_tree_call() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+fn feature_importance_call() -> Result<(), Box<dyn Error>> {
+    // Важливість ознак у дереві рішень (buy_computer.csv)
+    let mut x: Vec<Vec<String>> = Vec::new();
+    let mut y: Vec<String> = Vec::new();
+    let mut reader = Reader::from_path("datasets/buy_computer.csv")?;
+    let mut feature_count = 0;
+    for record in reader.records() {
+        let record = record?;
+        feature_count = record.len();
+
+        y.push(record.get(feature_count-1).unwrap().to_string());
+        x.push(record.iter().take(feature_count-1).map(|s| s.to_string()).collect());
+    }
+    let feature_names: Vec<&str> = reader.headers()?.iter().take(feature_count - 1).collect();
+
+    let mut tree_model = decision_tree::DecisionTreeClassifier::new();
+    tree_model.fit(&x, &y);
+    println!("Decision Tree feature importances:");
+    for (name, importance) in tree_model.named_feature_importances(&feature_names) {
+        println!("  {name}: {importance:.4}");
+    }
+
+    // Permutation importance для kNN на відкладеній вибірці vehicle.csv
+    let (x, y, names) = util::load_numeric_dataset("datasets/vehicle.csv", 0)?;
+    let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+    let mut knn_model = knn::KNNClassifier::new(3);
+    knn_model.fit(&util::select_rows(&x, &train), &util::select_rows(&y, &train));
+
+    let x_test = util::select_rows(&x, &test);
+    let y_test = util::select_rows(&y, &test);
+    let result = importance::permutation_importance(|data| knn_model.predict(data), &x_test, &y_test, 5, 3);
+    println!("kNN baseline accuracy: {:.4}", result.baseline_accuracy);
+    let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+    for (name, mean, std) in result.ranked(&names) {
+        println!("  {name}: {mean:.4} ± {std:.4}");
+    }
+    let selected: Vec<&str> = result.selected_features(0.0).into_iter().map(|i| names[i]).collect();
+    println!("Selected columns: {:?}", selected);
+    Ok(())
+}
+
+fn regression_tree_call() -> Result<(), Box<dyn Error>> {
+    // Прогноз числової ознаки compactness за рештою ознак vehicle.csv (включно з категоріальною class)
+    let mut x: Vec<Vec<String>> = Vec::new();
+    let mut y: Vec<f64> = Vec::new();
+    let mut reader = Reader::from_path("datasets/vehicle.csv")?;
+    for record in reader.records() {
+        let record = record?;
+        if record.iter().any(|val| val.is_empty()) {
+            continue;
+        }
+        y.push(record.get(0).unwrap().parse::<f64>()?);
+        x.push(record.iter().skip(1).map(|s| s.to_string()).collect());
+    }
+    let feature_names: Vec<&str> = reader.headers()?.iter().skip(1).collect();
+
+    let classes: Vec<String> = x.iter().map(|row| row[row.len() - 1].clone()).collect();
+    let (train, test) = util::train_test_split_indices(&classes, 0.3, 3);
+    let x_train = util::select_rows(&x, &train);
+    let y_train = util::select_rows(&y, &train);
+    let x_test = util::select_rows(&x, &test);
+    let y_test = util::select_rows(&y, &test);
+
+    let mse = |pred: &[f64]| pred.iter().zip(y_test.iter()).map(|(p, t)| (p - t).powi(2)).sum::<f64>() / y_test.len() as f64;
+
+    let mut tree_model = regression_tree::DecisionTreeRegressor::new().with_min_samples_leaf(5);
+    tree_model.fit(&x_train, &y_train);
+    println!("Regression tree: {} leaves, depth {}, test MSE {:.4}", tree_model.n_leaves(), tree_model.depth(), mse(&tree_model.predict(&x_test)));
+
+    let path = tree_model.cost_complexity_path();
+    let alpha = path[path.len() / 2];
+    tree_model.prune(alpha);
+    println!("Pruned (alpha = {:.4}): {} leaves, depth {}, test MSE {:.4}", alpha, tree_model.n_leaves(), tree_model.depth(), mse(&tree_model.predict(&x_test)));
+
+    tree_model.print_tree(&feature_names, "");
+    println!("{}", tree_model.export_dot(&feature_names));
+    Ok(())
+}
+
+fn random_forest_call() -> Result<(), Box<dyn Error>> {
+    let mut x: Vec<Vec<String>> = Vec::new();
+    let mut y: Vec<String> = Vec::new();
+    let mut reader = Reader::from_path("datasets/buy_computer.csv")?;
+    let mut feature_count = 0;
+    for record in reader.records() {
+        let record = record?;
+        feature_count = record.len();
+
+        y.push(record.get(feature_count-1).unwrap().to_string());
+        x.push(record.iter().take(feature_count-1).map(|s| s.to_string()).collect());
+    }
+    let feature_names: Vec<&str> = reader.headers()?.iter().take(feature_count - 1).collect();
+
+    let mut forest = random_forest::RandomForestClassifier::new(100, None, random_forest::Voting::Soft, 3);
+    forest.fit(&x, &y);
+
+    let new_examples = vec![
+        vec!["31-40".into(), "High".into(), "Yes".into(), "Fair".into()],
+        vec!["<=30".into(), "Medium".into(), "No".into(), "Excellent".into()],
+    ];
+    println!("Random Forest predictions: {:?}", forest.predict(&new_examples));
+    println!("Random Forest probabilities: {:?}", forest.predict_proba(&new_examples));
+    println!("Out-of-bag error: {:?}", forest.oob_error());
+    for (name, importance) in feature_names.iter().zip(forest.feature_importances()) {
+        println!("  {name}: {importance:.4}");
+    }
+    Ok(())
+}
+
+fn adaboost_call() -> Result<(), Box<dyn Error>> {
+    let mut x: Vec<Vec<String>> = Vec::new();
+    let mut y: Vec<String> = Vec::new();
+    let mut reader = Reader::from_path("datasets/buy_computer.csv")?;
+    for record in reader.records() {
+        let record = record?;
+        let feature_count = record.len();
+
+        y.push(record.get(feature_count-1).unwrap().to_string());
+        x.push(record.iter().take(feature_count-1).map(|s| s.to_string()).collect());
+    }
+
+    for (name, weak_learner) in [("stumps", adaboost::WeakLearner::Stump), ("OneR", adaboost::WeakLearner::OneR)] {
+        let mut model = adaboost::AdaBoostClassifier::new(20, 1.0, weak_learner);
+        model.fit(&x, &y);
+        println!("AdaBoost ({name}) alphas: {:?}", model.learner_weights());
+        // Помилка на навчальній вибірці залежно від кількості раундів
+        for (round, error) in model.staged_error(&x, &y).iter().enumerate() {
+            println!("  round {:>2}: error {:.4}", round + 1, error);
+        }
+    }
+    Ok(())
+}
+
+fn gradient_boosting_call() -> Result<(), Box<dyn Error>> {
+    let (x, y, _) = util::load_numeric_dataset("datasets/vehicle.csv", 0)?;
+    let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+    let x_train = util::select_rows(&x, &train);
+    let y_train = util::select_rows(&y, &train);
+    let x_test = util::select_rows(&x, &test);
+    let y_test = util::select_rows(&y, &test);
+
+    let mut gb_model = gradient_boosting::GradientBoostingClassifier::new(200, 0.1, 3, 3);
+    gb_model.subsample = 0.8;
+    gb_model.validation_fraction = 0.1;
+    gb_model.fit(&x_train, &y_train);
+    println!("Gradient Boosting: {} rounds, final validation loss {:?}", gb_model.n_rounds(), gb_model.validation_loss().last());
+    println!("Gradient Boosting accuracy: {:.4}", util::accuracy(&y_test, &gb_model.predict(&x_test)));
+
+    let mut knn_model = knn::KNNClassifier::new(3);
+    knn_model.fit(&x_train, &y_train);
+    println!("kNN accuracy: {:.4}", util::accuracy(&y_test, &knn_model.predict(&x_test)));
+    Ok(())
+}
+
+fn knn_backends_call() -> Result<(), Box<dyn Error>> {
+    use std::time::Instant;
+    use neighbors::Algorithm;
+
+    for (path, skip_columns) in [("datasets/iris.csv", 1), ("datasets/vehicle.csv", 0)] {
+        let (x, y, _) = util::load_numeric_dataset(path, skip_columns)?;
+        let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+        let x_train = util::select_rows(&x, &train);
+        let y_train = util::select_rows(&y, &train);
+        let x_test = util::select_rows(&x, &test);
+
+        for distance in [distance::Distance::Euclidean, distance::Distance::Manhattan, distance::Distance::Mahalanobis] {
+            let mut brute_force = knn::KNNClassifier::new(5).with_algorithm(Algorithm::BruteForce).with_distance(distance);
+            brute_force.fit(&x_train, &y_train);
+            let expected = brute_force.predict(&x_test);
+
+            for algorithm in [Algorithm::BruteForce, Algorithm::KdTree, Algorithm::BallTree, Algorithm::Auto] {
+                let mut knn_model = knn::KNNClassifier::new(5).with_algorithm(algorithm).with_distance(distance);
+                knn_model.fit(&x_train, &y_train);
+                let start = Instant::now();
+                let predictions = knn_model.predict(&x_test);
+                println!("{path} {:?} {:?} -> {:?}: {:?}, same as brute force: {}",
+                         distance, algorithm, knn_model.algorithm(), start.elapsed(), predictions == expected);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn knn_distances_call() -> Result<(), Box<dyn Error>> {
+    use distance::Distance;
+
+    // Числові дані: різні метрики на vehicle.csv
+    let (x, y, _) = util::load_numeric_dataset("datasets/vehicle.csv", 0)?;
+    let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+    let x_train = util::select_rows(&x, &train);
+    let y_train = util::select_rows(&y, &train);
+    let x_test = util::select_rows(&x, &test);
+    let y_test = util::select_rows(&y, &test);
+    for distance in [Distance::Euclidean, Distance::Manhattan, Distance::Chebyshev, Distance::Minkowski(3.0),
+                     Distance::Cosine, Distance::Mahalanobis, Distance::Heom, Distance::Hvdm] {
+        let mut knn_model = knn::KNNClassifier::new(5).with_distance(distance);
+        knn_model.fit(&x_train, &y_train);
+        let accuracy = util::accuracy(&y_test, &knn_model.predict(&x_test));
+        println!("vehicle.csv {:?} ({:?}): accuracy {:.4}", distance, knn_model.algorithm(), accuracy);
+    }
+
+    // Категоріальні дані: weather.csv та buy_computer.csv
+    for (path, example) in [
+        ("datasets/weather.csv", vec!["Sunny".to_string(), "Cool".to_string(), "Yes".to_string()]),
+        ("datasets/buy_computer.csv", vec!["31-40".to_string(), "High".to_string(), "Yes".to_string(), "Fair".to_string()]),
+    ] {
+        let mut x: Vec<Vec<String>> = Vec::new();
+        let mut y: Vec<String> = Vec::new();
+        let mut reader = Reader::from_path(path)?;
+        for record in reader.records() {
+            let record = record?;
+            let feature_count = record.len();
+            y.push(record.get(feature_count - 1).unwrap().to_string());
+            x.push(record.iter().take(feature_count - 1).map(|s| s.to_string()).collect());
+        }
+        for distance in [Distance::Hamming, Distance::Heom, Distance::Hvdm] {
+            let mut knn_model = knn::KNNClassifier::new(3).with_distance(distance);
+            knn_model.fit_categorical(&x, &y);
+            println!("{path} {:?}: {:?}", distance, knn_model.predict_categorical(std::slice::from_ref(&example)));
+        }
+    }
+    Ok(())
+}
+
+fn knn_weights_call() -> Result<(), Box<dyn Error>> {
+    let (x, y, _) = util::load_numeric_dataset("datasets/iris.csv", 1)?;
+    let test_points = vec![
+        vec![5.4, 3.9, 1.7, 0.4],
+        vec![6.0, 2.7, 4.9, 1.7],
+    ];
+
+    for weights in [knn::Weights::Uniform, knn::Weights::Distance, knn::Weights::Gaussian(0.5)] {
+        let mut knn_model = knn::KNNClassifier::new(7).with_weights(weights);
+        knn_model.fit(&x, &y);
+        println!("{:?}: {:?}", weights, knn_model.predict(&test_points));
+        println!("  probabilities: {:?}", knn_model.predict_proba(&test_points));
+    }
+
+    // Пояснення прогнозу: найближчі навчальні приклади
+    let mut knn_model = knn::KNNClassifier::new(5).with_weights(knn::Weights::Distance);
+    knn_model.fit(&x, &y);
+    for (point, neighbors) in test_points.iter().zip(knn_model.kneighbors(&test_points)) {
+        println!("Neighbours of {:?}:", point);
+        for neighbor in neighbors {
+            println!("  #{} {:?} ({}) at distance {:.4}", neighbor.index, x[neighbor.index], knn_model.neighbor_label(&neighbor), neighbor.distance);
+        }
+    }
+    Ok(())
+}
+
+fn knn_benchmark_call() -> Result<(), Box<dyn Error>> {
+    use std::time::Instant;
+    use neighbors::Algorithm;
+
+    // Прогноз для всієї вибірки vehicle.csv (кожен рядок як запит, 5 повторів) - різні бекенди та кількість потоків
+    let (x, y, _) = util::load_numeric_dataset("datasets/vehicle.csv", 0)?;
+    let queries: Vec<Vec<f64>> = (0..5).flat_map(|_| x.iter().cloned()).collect();
+    let mut job_counts = vec![1, 2, 4, std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)];
+    job_counts.sort();
+    job_counts.dedup();
+    for algorithm in [Algorithm::BruteForce, Algorithm::KdTree, Algorithm::BallTree] {
+        let mut reference: Option<Vec<String>> = None;
+        for &n_jobs in &job_counts {
+            let mut knn_model = knn::KNNClassifier::new(5).with_algorithm(algorithm).with_n_jobs(n_jobs);
+            knn_model.fit(&x, &y);
+            let start = Instant::now();
+            let predictions = knn_model.predict(&queries);
+            let elapsed = start.elapsed();
+            let same = reference.get_or_insert_with(|| predictions.clone()) == &predictions;
+            println!("{:?}, n_jobs = {n_jobs}: {} queries in {:?} ({:.1} µs/query), same as n_jobs = 1: {same}",
+                     algorithm, queries.len(), elapsed, elapsed.as_secs_f64() * 1e6 / queries.len() as f64);
+        }
+    }
+    Ok(())
+}
+
+fn knn_regression_call() -> Result<(), Box<dyn Error>> {
+    use knn::Weights;
+
+    // Регресія: ширина пелюстки ірису за трьома іншими вимірами
+    let (x, species, _) = util::load_numeric_dataset("datasets/iris.csv", 1)?;
+    let features: Vec<Vec<f64>> = x.iter().map(|row| row[..3].to_vec()).collect();
+    let targets: Vec<f64> = x.iter().map(|row| row[3]).collect();
+    let (train, test) = util::train_test_split_indices(&species, 0.3, 3);
+    let x_train = util::select_rows(&features, &train);
+    let x_test = util::select_rows(&features, &test);
+    let y_train = util::select_rows(&targets, &train);
+    let y_test = util::select_rows(&targets, &test);
+    for weights in [Weights::Uniform, Weights::Distance, Weights::Gaussian(0.5)] {
+        let mut regressor = knn::KNNRegressor::new(5).with_weights(weights);
+        regressor.fit(&x_train, &y_train);
+        let predictions = regressor.predict(&x_test);
+        let mse = predictions.iter().zip(y_test.iter()).map(|(p, t)| (p - t).powi(2)).sum::<f64>() / y_test.len() as f64;
+        println!("kNN regression {:?}: MSE = {:.4}, R^2 = {:.4}", weights, mse, regressor.score(&x_test, &y_test));
+    }
+
+    // Класифікація за радіусом: точки без сусідів у радіусі отримують мітку "outlier"
+    let x_train = util::select_rows(&x, &train);
+    let x_test = util::select_rows(&x, &test);
+    let y_train = util::select_rows(&species, &train);
+    let y_test = util::select_rows(&species, &test);
+    for radius in [0.2, 0.5, 1.0, 2.0] {
+        let mut classifier = knn::RadiusNeighborsClassifier::new(radius, "outlier");
+        classifier.fit(&x_train, &y_train);
+        let predictions = classifier.predict(&x_test);
+        let outliers = predictions.iter().filter(|p| *p == "outlier").count();
+        let mean_neighbors = classifier.radius_neighbors(&x_test).iter().map(|n| n.len()).sum::<usize>() as f64 / x_test.len() as f64;
+        println!("Radius {radius}: accuracy = {:.4}, outliers = {outliers}, mean neighbours = {:.1}",
+                 util::accuracy(&y_test, &predictions), mean_neighbors);
+    }
+    Ok(())
+}
+
+fn scaling_call() -> Result<(), Box<dyn Error>> {
+    use preprocessing::{Scaler, Scaling, Transformer};
+
+    // kNN на vehicle.csv без масштабування та з різними масштабуваннями (параметри - лише з навчальної частини)
+    let (x, y, _) = util::load_numeric_dataset("datasets/vehicle.csv", 0)?;
+    let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+    let x_train = util::select_rows(&x, &train);
+    let y_train = util::select_rows(&y, &train);
+    let x_test = util::select_rows(&x, &test);
+    let y_test = util::select_rows(&y, &test);
+
+    let mut knn_model = knn::KNNClassifier::new(5);
+    knn_model.fit(&x_train, &y_train);
+    println!("No scaling: accuracy = {:.4}", util::accuracy(&y_test, &knn_model.predict(&x_test)));
+
+    for scaling in [Scaling::Standard, Scaling::MinMax(0.0, 1.0), Scaling::Robust, Scaling::MaxAbs] {
+        let mut scaler = Scaler::new(scaling);
+        let x_train_scaled = scaler.fit_transform(&x_train);
+        let mut knn_model = knn::KNNClassifier::new(5);
+        knn_model.fit(&x_train_scaled, &y_train);
+
+        // Збережений і завантажений scaler дає ті самі ознаки
+        let path = std::env::temp_dir().join("vehicle_scaler.txt");
+        let path = path.to_str().unwrap();
+        scaler.save(path)?;
+        let restored = Scaler::load(path)?;
+        let predictions = knn_model.predict(&restored.transform(&x_test));
+        println!("{:?}: accuracy = {:.4}, restored scaler identical: {}",
+                 scaling, util::accuracy(&y_test, &predictions), restored.transform(&x_test) == scaler.transform(&x_test));
+    }
+    Ok(())
+}
+
+fn imputation_call() -> Result<(), Box<dyn Error>> {
+    use imputer::{KNNImputer, SimpleImputer, Strategy};
+    use preprocessing::Transformer;
+
+    let (x, y, feature_names) = util::load_numeric_dataset_with_missing("datasets/vehicle.csv", 0)?;
+    println!("Rows: {}, rows with missing values: {}", x.len(), x.iter().filter(|row| row.iter().any(|v| v.is_nan())).count());
+    for summary in imputer::missing_report(&x, &feature_names) {
+        if summary.missing > 0 {
+            println!("  {:<30} missing {:>3} ({:.2}%)", summary.column, summary.missing, summary.fraction * 100.0);
+        }
+    }
+
+    // Імпутер навчається лише на навчальній частині, масштабування - після імпутації
+    let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+    let x_train = util::select_rows(&x, &train);
+    let y_train = util::select_rows(&y, &train);
+    let x_test = util::select_rows(&x, &test);
+    let y_test = util::select_rows(&y, &test);
+    let imputers: Vec<(&str, Box<dyn Transformer>)> = vec![
+        ("mean", Box::new(SimpleImputer::new(Strategy::Mean))),
+        ("median", Box::new(SimpleImputer::new(Strategy::Median))),
+        ("most frequent", Box::new(SimpleImputer::new(Strategy::MostFrequent))),
+        ("constant 0", Box::new(SimpleImputer::new(Strategy::Constant(0.0)))),
+        ("kNN (k = 5)", Box::new(KNNImputer::new(5))),
+    ];
+    for (name, mut imputer) in imputers {
+        let train_filled = imputer.fit_transform(&x_train);
+        let test_filled = imputer.transform(&x_test);
+        let mut scaler = preprocessing::Scaler::new(preprocessing::Scaling::Standard);
+        let mut knn_model = knn::KNNClassifier::new(5);
+        knn_model.fit(&scaler.fit_transform(&train_filled), &y_train);
+        let predictions = knn_model.predict(&scaler.transform(&test_filled));
+        println!("{name}: accuracy = {:.4}", util::accuracy(&y_test, &predictions));
+    }
+
+    // Категоріальні дані: пропуск "?" заповнюється найчастішим значенням
+    let rows = vec![
+        vec!["sunny".to_string(), "hot".to_string()],
+        vec!["?".to_string(), "mild".to_string()],
+        vec!["sunny".to_string(), "".to_string()],
+        vec!["rainy".to_string(), "mild".to_string()],
+    ];
+    println!("Categorical imputation: {:?}", imputer::CategoricalImputer::most_frequent().fit_transform(&rows));
+    Ok(())
+}
+
+fn outliers_call() -> Result<(), Box<dyn Error>> {
+    use outliers::{OutlierCapper, OutlierRule};
+    use preprocessing::Transformer;
+
+    let (x, y, feature_names) = util::load_numeric_dataset("datasets/vehicle.csv", 0)?;
+    for rule in [OutlierRule::ZScore(3.0), OutlierRule::Iqr(1.5)] {
+        let report = outliers::detect_outliers(&x, rule);
+        println!("{:?}: {} of {} rows affected", rule, report.rows.len(), x.len());
+        for (name, count) in feature_names.iter().zip(report.per_column.iter()) {
+            if *count > 0 {
+                println!("  {:<30} {count}", name);
+            }
+        }
+    }
+
+    // Обрізання викидів перед масштабуванням і kNN (межі - лише з навчальної частини)
+    let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+    let x_train = util::select_rows(&x, &train);
+    let y_train = util::select_rows(&y, &train);
+    let x_test = util::select_rows(&x, &test);
+    let y_test = util::select_rows(&y, &test);
+    let cappers = [None, Some(OutlierCapper::new(1.5)), Some(OutlierCapper::new(3.0)), Some(OutlierCapper::new(0.0).with_percentiles(5.0, 95.0))];
+    for (i, capper) in cappers.into_iter().enumerate() {
+        let (train_capped, test_capped) = match capper {
+            Some(mut capper) => (capper.fit_transform(&x_train), capper.transform(&x_test)),
+            None => (x_train.clone(), x_test.clone()),
+        };
+        let mut scaler = preprocessing::Scaler::new(preprocessing::Scaling::Standard);
+        let mut knn_model = knn::KNNClassifier::new(5);
+        knn_model.fit(&scaler.fit_transform(&train_capped), &y_train);
+        let predictions = knn_model.predict(&scaler.transform(&test_capped));
+        let name = ["no capping", "IQR x1.5", "IQR x3", "5-95 percentiles"][i];
+        println!("{name}: accuracy = {:.4}", util::accuracy(&y_test, &predictions));
+    }
+    Ok(())
+}
+
+fn pipeline_call() -> Result<(), Box<dyn Error>> {
+    use imputer::{SimpleImputer, Strategy};
+    use outliers::OutlierCapper;
+    use pipeline::Pipeline;
+    use preprocessing::{Scaler, Scaling};
+
+    // impute -> outlier -> scale -> classifier, як у ноутбуці
+    let build = || {
+        Pipeline::new(knn::KNNClassifier::new(5))
+            .with_step("impute", SimpleImputer::new(Strategy::Median))
+            .with_step("outlier", OutlierCapper::new(1.5))
+            .with_step("scale", Scaler::new(Scaling::Standard))
+    };
+    let (x, y, _) = util::load_numeric_dataset_with_missing("datasets/vehicle.csv", 0)?;
+
+    let report = pipeline::cross_validate(build, &x, &y, 5, 3);
+    println!("5-fold CV accuracy: {:?}, mean = {:.4}", report.scores.iter().map(|s| format!("{s:.4}")).collect::<Vec<_>>(), report.mean());
+    report.print("kNN pipeline (5-fold CV)");
+
+    let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+    let x_train = util::select_rows(&x, &train);
+    let y_train = util::select_rows(&y, &train);
+    let x_test = util::select_rows(&x, &test);
+    let y_test = util::select_rows(&y, &test);
+    let mut model = build();
+    model.fit(&x_train, &y_train);
+    println!("Steps: {:?}, test accuracy = {:.4}", model.step_names(), model.score(&x_test, &y_test));
+
+    // Увесь ланцюжок зберігається як одна модель
+    let path = std::env::temp_dir().join("vehicle_pipeline.txt");
+    let path = path.to_str().unwrap();
+    model.save(path)?;
+    let restored = Pipeline::load(path)?;
+    println!("Restored pipeline: same predictions = {}", restored.predict(&x_test) == model.predict(&x_test));
+    Ok(())
+}
+
+fn pca_call() -> Result<(), Box<dyn Error>> {
+    use pca::{Components, Pca};
+    use pipeline::Pipeline;
+    use preprocessing::{Scaler, Scaling, Transformer};
+
+    let (x, y, _) = util::load_numeric_dataset("datasets/vehicle.csv", 0)?;
+    let mut scaler = Scaler::new(Scaling::Standard);
+    let mut pca_model = Pca::new(Components::All);
+    pca_model.fit(&scaler.fit_transform(&x));
+    let mut cumulative = 0.0;
+    for (i, (variance, ratio)) in pca_model.explained_variance().iter().zip(pca_model.explained_variance_ratio()).enumerate() {
+        cumulative += ratio;
+        println!("PC{:<2} variance = {:>7.4}, ratio = {:.4}, cumulative = {:.4}", i + 1, variance, ratio, cumulative);
+    }
+
+    // kNN і гаусів наївний Баєс з PCA та без (5-кратна перехресна перевірка)
+    let mean = |scores: Vec<f64>| scores.iter().sum::<f64>() / scores.len() as f64;
+    let configurations = [None, Some(Components::VarianceRatio(0.95)), Some(Components::Count(5))];
+    for components in configurations {
+        for whiten in [false, true] {
+            if components.is_none() && whiten {
+                continue;
+            }
+            let with_pca = |pipeline: Pipeline| {
+                let pipeline = pipeline.with_step("scale", Scaler::new(Scaling::Standard));
+                match components {
+                    Some(components) => pipeline.with_step("pca", Pca::new(components).with_whiten(whiten)),
+                    None => pipeline,
+                }
+            };
+            let knn_score = mean(pipeline::cross_val_score(|| with_pca(Pipeline::new(knn::KNNClassifier::new(5))), &x, &y, 5, 3));
+            let nb_score = mean(pipeline::cross_val_score(|| with_pca(Pipeline::new(naive_bayes::GaussianNaiveBayes::new())), &x, &y, 5, 3));
+            println!("PCA {:?}, whiten = {whiten}: kNN = {:.4}, Gaussian NB = {:.4}", components, knn_score, nb_score);
+        }
+    }
+    Ok(())
+}
+
+fn discretization_call() -> Result<(), Box<dyn Error>> {
+    use discretize::{Binning, Discretizer};
+
+    let binnings = [Binning::EqualWidth(5), Binning::EqualFrequency(5), Binning::OneR(6), Binning::Mdl];
+    for (path, skip_columns) in [("datasets/iris.csv", 1), ("datasets/vehicle.csv", 0)] {
+        let (x, y, feature_names) = util::load_numeric_dataset(path, skip_columns)?;
+        let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+        let x_train = util::select_rows(&x, &train);
+        let y_train = util::select_rows(&y, &train);
+        let x_test = util::select_rows(&x, &test);
+        let y_test = util::select_rows(&y, &test);
+
+        for binning in binnings {
+            // Межі інтервалів - лише з навчальної частини
+            let mut discretizer = Discretizer::new(binning);
+            let train_labels = discretizer.fit_transform(&x_train, &y_train);
+            let test_labels = discretizer.transform(&x_test);
+
+            let mut one_r = one_r::OneRClassifier::new();
+            one_r.fit(&train_labels, &y_train);
+            let mut tree = decision_tree::DecisionTreeClassifier::new();
+            tree.fit(&train_labels, &y_train);
+            let bins: usize = discretizer.cut_points().iter().map(|cuts| cuts.len() + 1).sum();
+            println!("{path} {:?}: {:.1} bins per feature, OneR = {:.4}, ID3 = {:.4}",
+                     binning, bins as f64 / feature_names.len() as f64,
+                     util::accuracy(&y_test, &one_r.predict(&test_labels)), util::accuracy(&y_test, &tree.predict(&test_labels)));
+        }
+    }
+
+    // Інтервали для довжини пелюстки ірису
+    let (x, y, feature_names) = util::load_numeric_dataset("datasets/iris.csv", 1)?;
+    for binning in binnings {
+        let mut discretizer = Discretizer::new(binning);
+        discretizer.fit(&x, &y);
+        let cuts = &discretizer.cut_points()[2];
+        let labels: Vec<String> = (0..=cuts.len()).map(|b| discretize::interval_label(cuts, b)).collect();
+        println!("{} {:?}: {:?}", feature_names[2], binning, labels);
+    }
+    Ok(())
+}
+
+fn one_r_numeric_call() -> Result<(), Box<dyn Error>> {
+    // Сирі рядки CSV: числові стовпці OneR сам ділить на інтервали
+    for (path, skip_columns) in [("datasets/iris.csv", 1), ("datasets/vehicle.csv", 0)] {
+        let mut x: Vec<Vec<String>> = Vec::new();
+        let mut y: Vec<String> = Vec::new();
+        let mut reader = Reader::from_path(path)?;
+        let feature_names: Vec<String> = reader.headers()?.iter().skip(skip_columns).map(|s| s.to_string()).collect();
+        for record in reader.records() {
+            let record = record?;
+            let fields: Vec<String> = record.iter().skip(skip_columns).map(|s| s.to_string()).collect();
+            y.push(fields[fields.len() - 1].clone());
+            x.push(fields[..fields.len() - 1].to_vec());
+        }
+        let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+        let x_train = util::select_rows(&x, &train);
+        let y_train = util::select_rows(&y, &train);
+        let x_test = util::select_rows(&x, &test);
+        let y_test = util::select_rows(&y, &test);
+
+        // 0 - кожне числове значення як окрема категорія (поведінка до підтримки числових ознак)
+        for min_bucket_size in [0, 3, 6, 12] {
+            let mut model = one_r::OneRClassifier::new().with_min_bucket_size(min_bucket_size);
+            model.fit(&x_train, &y_train);
+            println!("{path} min bucket {min_bucket_size:>2}: feature = {}, train accuracy = {:.4}, test accuracy = {:.4}",
+                     feature_names[model.get_best_feature_index()],
+                     util::accuracy(&y_train, &model.predict(&x_train)), util::accuracy(&y_test, &model.predict(&x_test)));
+        }
+    }
+    Ok(())
+}
+
+fn baseline_call() -> Result<(), Box<dyn Error>> {
+    use dummy::{DummyClassifier, DummyStrategy, ZeroRClassifier};
+    use pipeline::{Classifier, Pipeline};
+    use preprocessing::{Scaler, Scaling};
+
+    let (x, y, _) = util::load_numeric_dataset("datasets/vehicle.csv", 0)?;
+    let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+    let x_train = util::select_rows(&x, &train);
+    let y_train = util::select_rows(&y, &train);
+    let x_test = util::select_rows(&x, &test);
+    let y_test = util::select_rows(&y, &test);
+
+    let baseline = dummy::baseline_accuracy(&y_train, &y_test);
+    let mut zero_r = ZeroRClassifier::new();
+    zero_r.fit(&y_train);
+    println!("ZeroR predicts '{}'", zero_r.default_class());
+
+    let mut models: Vec<(&str, Box<dyn Classifier>)> = vec![
+        ("ZeroR", Box::new(zero_r)),
+        ("Dummy (stratified)", Box::new(DummyClassifier::new(DummyStrategy::Stratified, 3))),
+        ("Dummy (uniform)", Box::new(DummyClassifier::new(DummyStrategy::Uniform, 3))),
+        ("Gaussian NB", Box::new(naive_bayes::GaussianNaiveBayes::new())),
+        ("kNN (k = 5)", Box::new(knn::KNNClassifier::new(5))),
+    ];
+    for (name, model) in models.iter_mut() {
+        model.fit(&x_train, &y_train);
+        dummy::print_lift(name, util::accuracy(&y_test, &model.predict(&x_test)), baseline);
+    }
+
+    let mut scaled_knn = Pipeline::new(knn::KNNClassifier::new(5)).with_step("scale", Scaler::new(Scaling::Standard));
+    scaled_knn.fit(&x_train, &y_train);
+    dummy::print_lift("kNN (k = 5) + scaling", scaled_knn.score(&x_test, &y_test), baseline);
+
+    // OneR на тих самих даних у вигляді рядків (числові ознаки - інтервали Holte)
+    let to_strings = |rows: &[Vec<f64>]| -> Vec<Vec<String>> { rows.iter().map(|row| row.iter().map(|v| v.to_string()).collect()).collect() };
+    let mut one_r_model = one_r::OneRClassifier::new();
+    one_r_model.fit(&to_strings(&x_train), &y_train);
+    dummy::print_lift("OneR", util::accuracy(&y_test, &one_r_model.predict(&to_strings(&x_test))), baseline);
+
+    let mut boosting = gradient_boosting::GradientBoostingClassifier::new(100, 0.1, 3, 3);
+    boosting.fit(&x_train, &y_train);
+    dummy::print_lift("Gradient boosting", util::accuracy(&y_test, &boosting.predict(&x_test)), baseline);
+
+    // Те саме на 5-кратній перехресній перевірці: ZeroR навчається на кожній частині разом з моделлю
+    pipeline::cross_validate(|| Pipeline::new(naive_bayes::GaussianNaiveBayes::new()), &x, &y, 5, 3)
+        .print("Gaussian NB (5-fold CV)");
+    pipeline::cross_validate(|| Pipeline::new(knn::KNNClassifier::new(5)).with_step("scale", Scaler::new(Scaling::Standard)), &x, &y, 5, 3)
+        .print("kNN + scaling (5-fold CV)");
+    Ok(())
+}
+
+fn rule_learning_call() -> Result<(), Box<dyn Error>> {
+    use discretize::{Binning, Discretizer};
+
+    let mut x: Vec<Vec<String>> = Vec::new();
+    let mut y: Vec<String> = Vec::new();
+    let mut reader = Reader::from_path("datasets/weather.csv")?;
+    for record in reader.records() {
+        let record = record?;
+        let n = record.len();
+        x.push(record.iter().take(n - 1).map(|s| s.to_string()).collect());
+        y.push(record.get(n - 1).unwrap().to_string());
+    }
+    let headers = reader.headers()?.clone();
+    let feature_names: Vec<&str> = headers.iter().take(headers.len() - 1).collect();
+
+    let mut prism_model = prism::PrismClassifier::new();
+    prism_model.fit(&x, &y);
+    println!("PRISM:");
+    prism_model.print_rules(&feature_names);
+    let mut ripper_model = ripper::RipperClassifier::new();
+    ripper_model.fit(&x, &y);
+    println!("RIPPER:");
+    ripper_model.print_rules(&feature_names);
+
+    // Числові набори - після дискретизації MDL, межі лише з навчальної частини
+    for (path, skip_columns) in [("datasets/iris.csv", 1), ("datasets/vehicle.csv", 0)] {
+        let (x, y, feature_names) = util::load_numeric_dataset(path, skip_columns)?;
+        let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+        let y_train = util::select_rows(&y, &train);
+        let y_test = util::select_rows(&y, &test);
+        let mut discretizer = Discretizer::new(Binning::Mdl);
+        let train_labels = discretizer.fit_transform(&util::select_rows(&x, &train), &y_train);
+        let test_labels = discretizer.transform(&util::select_rows(&x, &test));
+
+        let mut one_r_model = one_r::OneRClassifier::new();
+        one_r_model.fit(&train_labels, &y_train);
+        let mut prism_model = prism::PrismClassifier::new();
+        prism_model.fit(&train_labels, &y_train);
+        let mut ripper_model = ripper::RipperClassifier::new();
+        ripper_model.fit(&train_labels, &y_train);
+        println!("{path}: OneR = {:.4}, PRISM = {:.4} ({} rules), RIPPER = {:.4} ({} rules)",
+                 util::accuracy(&y_test, &one_r_model.predict(&test_labels)),
+                 util::accuracy(&y_test, &prism_model.predict(&test_labels)), prism_model.rules().len(),
+                 util::accuracy(&y_test, &ripper_model.predict(&test_labels)), ripper_model.rules().len());
+        if path.contains("iris") {
+            let names: Vec<&str> = feature_names.iter().map(|name| name.as_str()).collect();
+            ripper_model.print_rules(&names);
+        }
+    }
+    Ok(())
+}
+
+fn logistic_regression_call() -> Result<(), Box<dyn Error>> {
+    use logistic::{LogisticRegression, Penalty, Solver};
+    use pipeline::Pipeline;
+    use preprocessing::{Scaler, Scaling, Transformer};
+
+    for (path, skip_columns) in [("datasets/iris.csv", 1), ("datasets/vehicle.csv", 0)] {
+        let (x, y, _) = util::load_numeric_dataset(path, skip_columns)?;
+        let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+        let x_train = util::select_rows(&x, &train);
+        let y_train = util::select_rows(&y, &train);
+        let x_test = util::select_rows(&x, &test);
+        let y_test = util::select_rows(&y, &test);
+
+        let configurations = [
+            ("L2, L-BFGS", Penalty::L2(0.01), Solver::Lbfgs),
+            ("L2, gradient descent", Penalty::L2(0.01), Solver::GradientDescent),
+            ("no penalty, L-BFGS", Penalty::None, Solver::Lbfgs),
+            ("L1", Penalty::L1(0.01), Solver::GradientDescent),
+        ];
+        for (name, penalty, solver) in configurations {
+            let model = LogisticRegression::new().with_penalty(penalty).with_solver(solver).with_max_iter(2000);
+            let mut pipeline = Pipeline::new(model).with_step("scale", Scaler::new(Scaling::Standard));
+            pipeline.fit(&x_train, &y_train);
+            println!("{path} {name}: accuracy = {:.4}", pipeline.score(&x_test, &y_test));
+        }
+        let mut knn_pipeline = Pipeline::new(knn::KNNClassifier::new(5)).with_step("scale", Scaler::new(Scaling::Standard));
+        knn_pipeline.fit(&x_train, &y_train);
+        println!("{path} kNN (k = 5): accuracy = {:.4}", knn_pipeline.score(&x_test, &y_test));
+    }
+
+    // Ваги та ймовірності на ірисі (ознаки масштабовані)
+    let (x, y, feature_names) = util::load_numeric_dataset("datasets/iris.csv", 1)?;
+    let mut scaler = Scaler::new(Scaling::Standard);
+    let scaled = scaler.fit_transform(&x);
+    for penalty in [Penalty::L2(0.01), Penalty::L1(0.05)] {
+        let mut model = LogisticRegression::new().with_penalty(penalty);
+        model.fit(&scaled, &y);
+        println!("{:?}: {} iterations, accuracy on training data = {:.4}", penalty, model.n_iter(), util::accuracy(&y, &model.predict(&scaled)));
+        for ((class, weights), intercept) in model.classes().iter().zip(model.coefficients()).zip(model.intercepts()) {
+            let terms: Vec<String> = feature_names.iter().zip(weights).map(|(name, w)| format!("{name} {w:+.3}")).collect();
+            println!("  {class}: intercept {intercept:+.3}, {}", terms.join(", "));
+        }
+    }
+    let mut model = LogisticRegression::new();
+    model.fit(&scaled, &y);
+    for (row, probabilities) in [0, 60, 120].iter().zip(model.predict_proba(&util::select_rows(&scaled, &[0, 60, 120]))) {
+        let formatted: Vec<String> = probabilities.iter().map(|p| format!("{p:.3}")).collect();
+        println!("row {row} ({}): {:?}", y[*row], formatted);
+    }
+
+    // Бінарна задача: Iris-versicolor проти решти - один вектор ваг
+    let binary: Vec<String> = y.iter().map(|label| if label == "Iris-versicolor" { label.clone() } else { "other".to_string() }).collect();
+    let mut model = LogisticRegression::new();
+    model.fit(&scaled, &binary);
+    println!("binary {:?}: weights for '{}' = {:?}, accuracy on training data = {:.4}", model.classes(), model.classes()[1],
+             model.coefficients()[0].iter().map(|w| format!("{w:.3}")).collect::<Vec<_>>(), util::accuracy(&binary, &model.predict(&scaled)));
+
+    let mut pipeline = Pipeline::new(LogisticRegression::new()).with_step("scale", Scaler::new(Scaling::Standard));
+    pipeline.fit(&x, &y);
+    let restored = Pipeline::from_text(&pipeline.to_text())?;
+    println!("restored pipeline gives the same predictions: {}", restored.predict(&x) == pipeline.predict(&x));
+    Ok(())
+}
+
+fn svm_call() -> Result<(), Box<dyn Error>> {
+    use pipeline::Pipeline;
+    use preprocessing::{Scaler, Scaling};
+    use svm::{Kernel, SvmClassifier, SvmSolver};
+
+    for (path, skip_columns) in [("datasets/iris.csv", 1), ("datasets/vehicle.csv", 0)] {
+        let (x, y, feature_names) = util::load_numeric_dataset(path, skip_columns)?;
+        let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+        let x_train = util::select_rows(&x, &train);
+        let y_train = util::select_rows(&y, &train);
+        let x_test = util::select_rows(&x, &test);
+        let y_test = util::select_rows(&y, &test);
+        let gamma = 1.0 / feature_names.len() as f64;
+
+        let mut knn_pipeline = Pipeline::new(knn::KNNClassifier::new(5)).with_step("scale", Scaler::new(Scaling::Standard));
+        knn_pipeline.fit(&x_train, &y_train);
+        println!("{path} kNN (k = 5): accuracy = {:.4}", knn_pipeline.score(&x_test, &y_test));
+
+        let configurations = [
+            ("linear, Pegasos", Kernel::Linear, SvmSolver::Pegasos),
+            ("linear, coordinate descent", Kernel::Linear, SvmSolver::CoordinateDescent),
+            ("linear, SMO", Kernel::Linear, SvmSolver::Smo),
+            ("RBF, SMO", Kernel::Rbf(gamma), SvmSolver::Smo),
+            ("polynomial (3), SMO", Kernel::Polynomial { degree: 3, gamma, coef0: 1.0 }, SvmSolver::Smo),
+        ];
+        for (name, kernel, solver) in configurations {
+            for c in [0.1, 1.0, 10.0] {
+                let model = SvmClassifier::new(c).with_kernel(kernel).with_solver(solver);
+                let mut pipeline = Pipeline::new(model).with_step("scale", Scaler::new(Scaling::Standard));
+                pipeline.fit(&x_train, &y_train);
+                println!("{path} SVM {name}, C = {c}: accuracy = {:.4}", pipeline.score(&x_test, &y_test));
+            }
+        }
+    }
+
+    // Опорні вектори та збереження моделі з RBF-ядром
+    let (x, y, _) = util::load_numeric_dataset("datasets/iris.csv", 1)?;
+    let mut pipeline = Pipeline::new(SvmClassifier::new(1.0).with_kernel(Kernel::Rbf(0.25))).with_step("scale", Scaler::new(Scaling::Standard));
+    pipeline.fit(&x, &y);
+    let restored = Pipeline::from_text(&pipeline.to_text())?;
+    println!("restored pipeline gives the same predictions: {}", restored.predict(&x) == pipeline.predict(&x));
+
+    let mut model = SvmClassifier::new(1.0).with_kernel(Kernel::Rbf(0.25));
+    model.fit(&x, &y);
+    println!("support vectors per one-vs-rest machine {:?}: {:?}", model.classes(), model.n_support());
+    Ok(())
+}
+
+fn mlp_call() -> Result<(), Box<dyn Error>> {
+    use mlp::{Activation, MlpClassifier, Optimizer};
+    use pipeline::{cross_val_score, Pipeline};
+    use preprocessing::{Scaler, Scaling};
+
+    for (path, skip_columns) in [("datasets/iris.csv", 1), ("datasets/vehicle.csv", 0)] {
+        let (x, y, _) = util::load_numeric_dataset(path, skip_columns)?;
+        let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+        let x_train = util::select_rows(&x, &train);
+        let y_train = util::select_rows(&y, &train);
+        let x_test = util::select_rows(&x, &test);
+        let y_test = util::select_rows(&y, &test);
+        let baseline = dummy::baseline_accuracy(&y_train, &y_test);
+        println!("{path}:");
+
+        let mut knn_pipeline = Pipeline::new(knn::KNNClassifier::new(5)).with_step("scale", Scaler::new(Scaling::Standard));
+        knn_pipeline.fit(&x_train, &y_train);
+        dummy::print_lift("kNN (k = 5)", knn_pipeline.score(&x_test, &y_test), baseline);
+
+        let models = [
+            ("MLP (32) ReLU, Adam", MlpClassifier::new(&[32]).with_learning_rate(0.01)),
+            ("MLP (32, 16) tanh, Adam", MlpClassifier::new(&[32, 16]).with_activation(Activation::Tanh).with_learning_rate(0.01)),
+            ("MLP (32) ReLU, SGD", MlpClassifier::new(&[32]).with_optimizer(Optimizer::Sgd { momentum: 0.9 }).with_learning_rate(0.01)),
+            ("MLP (64) early stopping", MlpClassifier::new(&[64]).with_learning_rate(0.01).with_early_stopping(0.15, 20)),
+        ];
+        for (name, model) in models {
+            let mut pipeline = Pipeline::new(model).with_step("scale", Scaler::new(Scaling::Standard));
+            pipeline.fit(&x_train, &y_train);
+            dummy::print_lift(name, pipeline.score(&x_test, &y_test), baseline);
+        }
+    }
+
+    // Перехресна перевірка: масштабування навчається в кожній частині окремо
+    let (x, y, _) = util::load_numeric_dataset("datasets/iris.csv", 1)?;
+    let scores = cross_val_score(|| Pipeline::new(MlpClassifier::new(&[16]).with_learning_rate(0.01))
+                                     .with_step("scale", Scaler::new(Scaling::Standard)), &x, &y, 5, 3);
+    println!("iris 5-fold MLP (16): {:?}, mean = {:.4}", scores.iter().map(|s| format!("{s:.3}")).collect::<Vec<_>>(), scores.iter().sum::<f64>() / scores.len() as f64);
+
+    let mut model = MlpClassifier::new(&[16]).with_learning_rate(0.01).with_early_stopping(0.2, 15).with_seed(7);
+    let scaled = preprocessing::Transformer::fit_transform(&mut Scaler::new(Scaling::Standard), &x);
+    model.fit(&scaled, &y);
+    let curve = model.loss_curve();
+    println!("{} epochs, loss {:.4} -> {:.4}, best validation accuracy {:.4}", model.n_epochs(), curve[0], curve[curve.len() - 1],
+             model.validation_scores().iter().cloned().fold(0.0, f64::max));
+
+    let mut pipeline = Pipeline::new(MlpClassifier::new(&[8, 8]).with_learning_rate(0.01)).with_step("scale", Scaler::new(Scaling::Standard));
+    pipeline.fit(&x, &y);
+    let restored = Pipeline::from_text(&pipeline.to_text())?;
+    println!("restored pipeline gives the same predictions: {}", restored.predict(&x) == pipeline.predict(&x));
+    Ok(())
+}
+
+fn online_learning_call() -> Result<(), Box<dyn Error>> {
+    use online::{OnlineAlgorithm, OnlineClassifier};
+
+    let mut x: Vec<String> = Vec::new();
+    let mut y: Vec<String> = Vec::new();
+    let mut reader = Reader::from_path("datasets/spam.csv")?;
+    for record in reader.records() {
+        let record = record?;
+        y.push(record.get(0).unwrap().to_string());
+        x.push(record.iter().skip(1).collect::<Vec<&str>>().join(""));
+    }
+
+    let algorithms = [
+        ("Perceptron", OnlineAlgorithm::Perceptron),
+        ("Averaged perceptron", OnlineAlgorithm::AveragedPerceptron),
+        ("Passive-aggressive", OnlineAlgorithm::PassiveAggressive(1.0)),
+        ("Winnow", OnlineAlgorithm::Winnow(2.0)),
+    ];
+    let test_messages = [
+        "Congratulations! You've won a free iPhone. Click here to claim.",
+        "Are we still meeting for lunch tomorrow?",
+        "Claim your free cash prize now!",
+    ];
+    for (name, algorithm) in algorithms {
+        // Потік: спершу передбачення, потім навчання на тому самому повідомленні
+        let mut model = OnlineClassifier::new(algorithm, "Spam");
+        let mut online_correct = 0;
+        for _ in 0..5 {
+            for (text, label) in x.iter().zip(y.iter()) {
+                if model.predict(text) == (label == "Spam") {
+                    online_correct += 1;
+                }
+                model.partial_fit(text, label);
+            }
+        }
+        let training_correct = x.iter().zip(y.iter()).filter(|(text, label)| model.predict(text) == (*label == "Spam")).count();
+        println!("{name}: {} mistakes in {} updates, prequential accuracy {:.3}, training accuracy {:.3}",
+                 model.mistakes(), model.seen(), online_correct as f64 / model.seen() as f64, training_correct as f64 / x.len() as f64);
+        let predictions: Vec<bool> = test_messages.iter().map(|text| model.predict(text)).collect();
+        println!("  spam predictions: {:?}, top tokens: {:?}", predictions,
+                 model.top_tokens(5).iter().map(|(token, weight)| format!("{token} {weight:.2}")).collect::<Vec<_>>());
+    }
+
+    // Модель дооновлюється новими повідомленнями без повторного навчання
+    let mut model = OnlineClassifier::new(OnlineAlgorithm::PassiveAggressive(1.0), "Spam").with_epochs(5);
+    model.fit(&x, &y);
+    let message = "Exclusive gift for you: claim your reward";
+    println!("before update: {:.3}", model.decision_function(message));
+    model.partial_fit("Exclusive reward waiting, claim your gift", "Spam");
+    println!("after update: {:.3}", model.decision_function(message));
+
+    let mut nb_model = naive_bayes::NaiveBayesClassifier::new(1.);
+    nb_model.fit(&x, &y);
+    println!("naive_bayes spam probabilities: {:?}", test_messages.iter().map(|text| format!("{:.3}", nb_model.predict(text))).collect::<Vec<_>>());
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
-    // one_r_test_call()
-    knn_test_call()
-    // decision_tree_call()
-    // naive_bayes_test_call()
+    // Демо обирається першим аргументом: cargo run --release -- one_r (без аргументу - kNN)
+    match std::env::args().nth(1).as_deref() {
+        Some("one_r") => one_r_test_call(),
+        None | Some("knn") => knn_test_call(),
+        Some("decision_tree") => decision_tree_call(),
+        Some("naive_bayes") => naive_bayes_test_call(),
+        Some("feature_importance") => feature_importance_call(),
+        Some("regression_tree") => regression_tree_call(),
+        Some("random_forest") => random_forest_call(),
+        Some("adaboost") => adaboost_call(),
+        Some("gradient_boosting") => gradient_boosting_call(),
+        Some("knn_backends") => knn_backends_call(),
+        Some("knn_distances") => knn_distances_call(),
+        Some("knn_weights") => knn_weights_call(),
+        Some("knn_benchmark") => knn_benchmark_call(),
+        Some("knn_regression") => knn_regression_call(),
+        Some("scaling") => scaling_call(),
+        Some("imputation") => imputation_call(),
+        Some("outliers") => outliers_call(),
+        Some("pipeline") => pipeline_call(),
+        Some("pca") => pca_call(),
+        Some("discretization") => discretization_call(),
+        Some("one_r_numeric") => one_r_numeric_call(),
+        Some("baseline") => baseline_call(),
+        Some("rule_learning") => rule_learning_call(),
+        Some("logistic_regression") => logistic_regression_call(),
+        Some("svm") => svm_call(),
+        Some("mlp") => mlp_call(),
+        Some("online_learning") => online_learning_call(),
+        Some(other) => Err(format!("невідоме демо: {other}").into()),
+    }
 }
@@ -0,0 +1,429 @@
+use std::error::Error;
+use crate::pipeline::Classifier;
+use crate::preprocessing::{parse_values_line, values_line};
+use crate::util::{accuracy, dot, select_rows, softmax, train_test_split_indices, Rng};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Activation {
+    Relu,
+    Tanh,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Optimizer {
+    // міні-пакетний SGD з імпульсом
+    Sgd { momentum: f64 },
+    // Adam (Kingma & Ba, 2014) з beta1 = 0.9, beta2 = 0.999
+    Adam,
+}
+
+// Рання зупинка: частина навчальної вибірки відкладається для перевірки; навчання зупиняється,
+// якщо точність на ній не зростає patience епох, і повертаються ваги з найкращою точністю.
+// Якщо відкладена частина порожня (мала вибірка), зупинка - за втратами, як без ранньої зупинки.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct EarlyStopping {
+    pub validation_fraction: f64,
+    pub patience: usize,
+}
+
+// Багатошаровий перцептрон: приховані шари з ReLU або tanh, вихідний шар softmax, перехресна ентропія з L2-штрафом alpha.
+// Ваги всіх шарів зберігаються в одному векторі: для кожного шару матриця [вихід][вхід], потім зсуви.
+pub struct MlpClassifier {
+    hidden_layers: Vec<usize>,
+    activation: Activation,
+    optimizer: Optimizer,
+    learning_rate: f64,
+    batch_size: usize,
+    max_epochs: usize,
+    alpha: f64,
+    tolerance: f64,
+    early_stopping: Option<EarlyStopping>,
+    seed: u64,
+    classes: Vec<String>,
+    layer_sizes: Vec<usize>,
+    params: Vec<f64>,
+    loss_curve: Vec<f64>,
+    validation_scores: Vec<f64>,
+}
+
+// Якщо втрати (без ранньої зупинки) не зменшуються на tolerance стільки епох поспіль - навчання завершується
+const NO_IMPROVEMENT_EPOCHS: usize = 10;
+
+impl Activation {
+    fn apply(self, z: f64) -> f64 {
+        match self {
+            Activation::Relu => z.max(0.0),
+            Activation::Tanh => z.tanh(),
+        }
+    }
+
+    // Похідна, виражена через значення активації
+    fn derivative(self, activated: f64) -> f64 {
+        match self {
+            Activation::Relu => if activated > 0.0 { 1.0 } else { 0.0 },
+            Activation::Tanh => 1.0 - activated * activated,
+        }
+    }
+}
+
+impl MlpClassifier {
+    pub fn new(hidden_layers: &[usize]) -> Self {
+        MlpClassifier {
+            hidden_layers: hidden_layers.to_vec(),
+            activation: Activation::Relu,
+            optimizer: Optimizer::Adam,
+            learning_rate: 0.001,
+            batch_size: 32,
+            max_epochs: 200,
+            alpha: 1e-4,
+            tolerance: 1e-4,
+            early_stopping: None,
+            seed: 1,
+            classes: Vec::new(),
+            layer_sizes: Vec::new(),
+            params: Vec::new(),
+            loss_curve: Vec::new(),
+            validation_scores: Vec::new(),
+        }
+    }
+
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    pub fn with_optimizer(mut self, optimizer: Optimizer) -> Self {
+        self.optimizer = optimizer;
+        self
+    }
+
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_max_epochs(mut self, max_epochs: usize) -> Self {
+        self.max_epochs = max_epochs;
+        self
+    }
+
+    // Сила L2-регуляризації ваг (зсуви не штрафуються)
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_early_stopping(mut self, validation_fraction: f64, patience: usize) -> Self {
+        self.early_stopping = Some(EarlyStopping { validation_fraction, patience });
+        self
+    }
+
+    // Зерно для початкових ваг, порядку прикладів і відкладеної частини
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // Зміщення ваг і зсувів кожного шару у векторі параметрів
+    fn layer_offsets(&self) -> Vec<(usize, usize)> {
+        let mut offset = 0;
+        self.layer_sizes.windows(2)
+            .map(|sizes| {
+                let weights = offset;
+                offset += sizes[0] * sizes[1];
+                let biases = offset;
+                offset += sizes[1];
+                (weights, biases)
+            })
+            .collect()
+    }
+
+    // Активації всіх шарів (перший - сам приклад, останній - ймовірності класів)
+    fn forward(&self, params: &[f64], offsets: &[(usize, usize)], input: &[f64]) -> Vec<Vec<f64>> {
+        let mut activations = vec![input.to_vec()];
+        let last = offsets.len() - 1;
+        for (l, &(weights, biases)) in offsets.iter().enumerate() {
+            let (fan_in, fan_out) = (self.layer_sizes[l], self.layer_sizes[l + 1]);
+            let previous = &activations[l];
+            let mut output: Vec<f64> = (0..fan_out)
+                .map(|j| params[biases + j] + dot(&params[weights + j * fan_in..weights + (j + 1) * fan_in], previous))
+                .collect();
+            if l == last {
+                softmax(&mut output);
+            } else {
+                output.iter_mut().for_each(|z| *z = self.activation.apply(*z));
+            }
+            activations.push(output);
+        }
+        activations
+    }
+
+    // Зворотне поширення похибки одного прикладу з накопиченням градієнта; повертає перехресну ентропію
+    fn backward(&self, params: &[f64], offsets: &[(usize, usize)], input: &[f64], target: usize, gradient: &mut [f64]) -> f64 {
+        let activations = self.forward(params, offsets, input);
+        let probabilities = &activations[activations.len() - 1];
+        let loss = -probabilities[target].max(1e-300).ln();
+        let mut delta: Vec<f64> = probabilities.iter().enumerate().map(|(c, p)| p - (c == target) as usize as f64).collect();
+        for (l, &(weights, biases)) in offsets.iter().enumerate().rev() {
+            let fan_in = self.layer_sizes[l];
+            let previous = &activations[l];
+            for (j, d) in delta.iter().enumerate() {
+                gradient[biases + j] += d;
+                gradient[weights + j * fan_in..weights + (j + 1) * fan_in].iter_mut().zip(previous).for_each(|(g, a)| *g += d * a);
+            }
+            if l > 0 {
+                delta = (0..fan_in)
+                    .map(|i| delta.iter().enumerate().map(|(j, d)| params[weights + j * fan_in + i] * d).sum::<f64>() * self.activation.derivative(previous[i]))
+                    .collect();
+            }
+        }
+        loss
+    }
+
+    pub fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        let mut classes: Vec<String> = y.to_vec();
+        classes.sort();
+        classes.dedup();
+        let targets: Vec<usize> = y.iter().map(|label| classes.binary_search(label).unwrap()).collect();
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        self.layer_sizes = std::iter::once(dims).chain(self.hidden_layers.iter().copied()).chain(std::iter::once(classes.len())).collect();
+        self.classes = classes;
+        let offsets = self.layer_offsets();
+
+        // Ініціалізація Глорота: U(-b, b), b = sqrt(6 / (fan_in + fan_out)); зсуви - так само
+        let mut rng = Rng::new(self.seed);
+        self.params.clear();
+        for sizes in self.layer_sizes.windows(2) {
+            let bound = (6.0 / (sizes[0] + sizes[1]) as f64).sqrt();
+            for _ in 0..(sizes[0] + 1) * sizes[1] {
+                self.params.push((2.0 * rng.next_f64() - 1.0) * bound);
+            }
+        }
+        let is_weight: Vec<bool> = self.layer_sizes.windows(2)
+            .flat_map(|sizes| std::iter::repeat_n(true, sizes[0] * sizes[1]).chain(std::iter::repeat_n(false, sizes[1])))
+            .collect();
+
+        let (train, validation) = match self.early_stopping {
+            Some(stopping) => train_test_split_indices(y, stopping.validation_fraction, self.seed),
+            None => ((0..x.len()).collect(), Vec::new()),
+        };
+        let x_validation = select_rows(x, &validation);
+        let y_validation = select_rows(y, &validation);
+        let early_stopping = self.early_stopping.filter(|_| !validation.is_empty());
+
+        let mut first_moment = vec![0.0; self.params.len()];
+        let mut second_moment = vec![0.0; self.params.len()];
+        let mut step = 0;
+        let mut order = train;
+        let mut best_loss = f64::INFINITY;
+        let mut best_score = f64::NEG_INFINITY;
+        let mut best_params = self.params.clone();
+        let mut epochs_without_improvement = 0;
+        self.loss_curve.clear();
+        self.validation_scores.clear();
+        for _ in 0..self.max_epochs {
+            rng.shuffle(&mut order);
+            let mut epoch_loss = 0.0;
+            for batch in order.chunks(self.batch_size) {
+                let mut gradient = vec![0.0; self.params.len()];
+                for &i in batch {
+                    epoch_loss += self.backward(&self.params, &offsets, &x[i], targets[i], &mut gradient);
+                }
+                let batch_len = batch.len() as f64;
+                for ((g, w), &weight) in gradient.iter_mut().zip(&self.params).zip(&is_weight) {
+                    *g = (*g + if weight { self.alpha * w } else { 0.0 }) / batch_len;
+                }
+                step += 1;
+                match self.optimizer {
+                    Optimizer::Sgd { momentum } => {
+                        for ((p, v), g) in self.params.iter_mut().zip(first_moment.iter_mut()).zip(&gradient) {
+                            *v = momentum * *v - self.learning_rate * g;
+                            *p += *v;
+                        }
+                    }
+                    Optimizer::Adam => {
+                        let correction1 = 1.0 - 0.9f64.powi(step);
+                        let correction2 = 1.0 - 0.999f64.powi(step);
+                        for (((p, m), v), g) in self.params.iter_mut().zip(first_moment.iter_mut()).zip(second_moment.iter_mut()).zip(&gradient) {
+                            *m = 0.9 * *m + 0.1 * g;
+                            *v = 0.999 * *v + 0.001 * g * g;
+                            *p -= self.learning_rate * (*m / correction1) / ((*v / correction2).sqrt() + 1e-8);
+                        }
+                    }
+                }
+            }
+            let penalty: f64 = self.params.iter().zip(&is_weight).filter(|(_, &weight)| weight).map(|(w, _)| w * w).sum::<f64>();
+            let epoch_loss = (epoch_loss + 0.5 * self.alpha * penalty) / order.len().max(1) as f64;
+            self.loss_curve.push(epoch_loss);
+
+            let improved = if early_stopping.is_some() {
+                let score = accuracy(&y_validation, &self.predict(&x_validation));
+                self.validation_scores.push(score);
+                let improved = score > best_score + self.tolerance;
+                if score > best_score {
+                    best_score = score;
+                    best_params = self.params.clone();
+                }
+                improved
+            } else {
+                let improved = epoch_loss < best_loss - self.tolerance;
+                best_loss = best_loss.min(epoch_loss);
+                improved
+            };
+            epochs_without_improvement = if improved { 0 } else { epochs_without_improvement + 1 };
+            let patience = early_stopping.map(|stopping| stopping.patience).unwrap_or(NO_IMPROVEMENT_EPOCHS);
+            if epochs_without_improvement >= patience {
+                break;
+            }
+        }
+        if early_stopping.is_some() {
+            self.params = best_params;
+        }
+    }
+
+    // Ймовірності класів у порядку classes()
+    pub fn predict_proba(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let offsets = self.layer_offsets();
+        x.iter().map(|row| self.forward(&self.params, &offsets, row).pop().unwrap_or_default()).collect()
+    }
+
+    pub fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        self.predict_proba(x)
+            .into_iter()
+            .map(|probabilities| {
+                let best = (0..probabilities.len()).fold(0, |best, c| if probabilities[c] > probabilities[best] { c } else { best });
+                self.classes[best].clone()
+            })
+            .collect()
+    }
+
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    // Середня втрата на навчальних прикладах після кожної епохи
+    pub fn loss_curve(&self) -> &[f64] {
+        &self.loss_curve
+    }
+
+    // Точність на відкладеній частині після кожної епохи (лише з ранньою зупинкою)
+    pub fn validation_scores(&self) -> &[f64] {
+        &self.validation_scores
+    }
+
+    pub fn n_epochs(&self) -> usize {
+        self.loss_curve.len()
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines().peekable();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let activation = match header.as_slice() {
+            ["mlp", "relu"] => Activation::Relu,
+            ["mlp", "tanh"] => Activation::Tanh,
+            _ => return Err(format!("невідомий заголовок MLP: {:?}", header).into()),
+        };
+        let mut model = MlpClassifier::new(&[]).with_activation(activation);
+        while let Some(class) = lines.peek().and_then(|line| line.strip_prefix("class ")) {
+            model.classes.push(class.to_string());
+            lines.next();
+        }
+        model.layer_sizes = parse_values_line(lines.next(), "layers")?.into_iter().map(|size| size as usize).collect();
+        model.params = parse_values_line(lines.next(), "params")?;
+        let expected: usize = model.layer_sizes.windows(2).map(|sizes| (sizes[0] + 1) * sizes[1]).sum();
+        if model.layer_sizes.len() < 2 || model.params.len() != expected {
+            return Err("кількість параметрів MLP не відповідає розмірам шарів".into());
+        }
+        model.hidden_layers = model.layer_sizes[1..model.layer_sizes.len() - 1].to_vec();
+        Ok(model)
+    }
+}
+
+impl Classifier for MlpClassifier {
+    fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        MlpClassifier::fit(self, x, y);
+    }
+
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        MlpClassifier::predict(self, x)
+    }
+
+    fn to_text(&self) -> String {
+        let activation = match self.activation {
+            Activation::Relu => "relu",
+            Activation::Tanh => "tanh",
+        };
+        let mut text = format!("mlp {activation}\n");
+        for class in &self.classes {
+            text.push_str(&format!("class {class}\n"));
+        }
+        let sizes: Vec<f64> = self.layer_sizes.iter().map(|&size| size as f64).collect();
+        text.push_str(&format!("{}\n{}\n", values_line("layers", &sizes), values_line("params", &self.params)));
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::load_classifier;
+    use crate::util::load_numeric_dataset;
+
+    // 4 приклади на клас: частка 0.1 округлюється до нуля, тож зупинка відбувається за втратами
+    #[test]
+    fn empty_validation_set_falls_back_to_loss() {
+        let x = vec![
+            vec![0.0, 0.0], vec![0.2, 0.1], vec![0.1, 0.3], vec![0.3, 0.2],
+            vec![2.0, 2.0], vec![2.2, 1.9], vec![1.8, 2.1], vec![2.1, 2.2],
+        ];
+        let y: Vec<String> = ["a", "a", "a", "a", "b", "b", "b", "b"].iter().map(|s| s.to_string()).collect();
+        let mut model = MlpClassifier::new(&[4]).with_early_stopping(0.1, 3).with_max_epochs(300);
+        model.fit(&x, &y);
+        assert!(model.validation_scores().is_empty());
+        assert!(model.n_epochs() > 4);
+        assert_eq!(model.predict(&x), y);
+    }
+
+    #[test]
+    fn round_trip() {
+        let (x, y, _) = load_numeric_dataset("datasets/iris.csv", 1).unwrap();
+        for activation in [Activation::Relu, Activation::Tanh] {
+            let mut model = MlpClassifier::new(&[8, 4]).with_activation(activation).with_max_epochs(50);
+            model.fit(&x, &y);
+            let text = Classifier::to_text(&model);
+            let restored = MlpClassifier::from_text(&text).unwrap();
+            assert_eq!(restored.predict_proba(&x), model.predict_proba(&x));
+            assert_eq!(Classifier::to_text(&restored), text);
+            assert_eq!(load_classifier(&text).unwrap().predict(&x), model.predict(&x));
+        }
+    }
+
+    // Після зупинки відновлюються параметри епохи з найкращою точністю на валідації
+    #[test]
+    fn early_stopping_restores_best_params() {
+        let (x, y, _) = load_numeric_dataset("datasets/iris.csv", 1).unwrap();
+        let mut model = MlpClassifier::new(&[8]).with_early_stopping(0.3, 5).with_seed(2).with_learning_rate(0.05).with_max_epochs(500);
+        model.fit(&x, &y);
+        assert_eq!(model.validation_scores().len(), model.loss_curve().len());
+        assert!(model.n_epochs() < 500);
+
+        // остання епоха гірша за найкращу, тож перевірка справді розрізняє відновлені параметри
+        let best = model.validation_scores().iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        assert!(*model.validation_scores().last().unwrap() < best);
+        // той самий поділ, що й у fit
+        let (_, validation) = train_test_split_indices(&y, 0.3, 2);
+        assert!(!validation.is_empty());
+        let score = accuracy(&select_rows(&y, &validation), &model.predict(&select_rows(&x, &validation)));
+        assert_eq!(score, best);
+    }
+}
@@ -1,5 +1,8 @@
 use std::collections::{HashMap, HashSet};
+use std::error::Error;
 use regex::Regex;
+use crate::pipeline::Classifier;
+use crate::preprocessing::{parse_values_line, values_line};
 
 pub fn tokenize(lower_case_text: &str) -> HashSet<&str> {
     Regex::new(r"[a-z0-9']+")
@@ -35,7 +38,7 @@ impl NaiveBayesClassifier {
         }
     }
 
-    pub fn fit(&mut self, x: &Vec<String>, y: &Vec<String>) {
+    pub fn fit(&mut self, x: &[String], y: &[String]) {
         let messages: Vec<Message> = x.iter()
             .zip(y.iter())
             .map(|(text, label)| Message {
@@ -93,7 +96,7 @@ impl NaiveBayesClassifier {
         let message_tokens = tokenize(&lower_case_text);
         let (prob_if_spam, prob_if_ham) = self.probabilities_of_message(message_tokens);
 
-        return prob_if_spam / (prob_if_spam + prob_if_ham);
+        prob_if_spam / (prob_if_spam + prob_if_ham)
     }
 
     fn probabilities_of_message(&self, message_tokens: HashSet<&str>) -> (f64, f64) {
@@ -101,7 +104,7 @@ impl NaiveBayesClassifier {
         let mut log_prob_if_ham = 0.;
 
         for token in self.tokens.iter() {
-            let (prob_if_spam, prob_if_ham) = self.probabilites_of_token(&token);
+            let (prob_if_spam, prob_if_ham) = self.probabilites_of_token(token);
 
             if message_tokens.contains(token.as_str()) {
                 log_prob_if_spam += prob_if_spam.ln();
@@ -115,7 +118,7 @@ impl NaiveBayesClassifier {
         let prob_if_spam = log_prob_if_spam.exp();
         let prob_if_ham = log_prob_if_ham.exp();
 
-        return (prob_if_spam, prob_if_ham);
+        (prob_if_spam, prob_if_ham)
     }
 
     fn probabilites_of_token(&self, token: &str) -> (f64, f64) {
@@ -125,6 +128,147 @@ impl NaiveBayesClassifier {
         let prob_of_token_ham = (self.token_ham_counts[token] as f64 + self.alpha)
             / (self.ham_messages_count as f64 + 2. * self.alpha);
 
-        return (prob_of_token_spam, prob_of_token_ham);
+        (prob_of_token_spam, prob_of_token_ham)
+    }
+}
+
+// Гаусів наївний Баєс для числових ознак: у кожному класі ознаки незалежні й нормально розподілені
+pub struct GaussianNaiveBayes {
+    classes: Vec<String>,
+    log_priors: Vec<f64>,
+    means: Vec<Vec<f64>>,  // means[клас][ознака]
+    variances: Vec<Vec<f64>>,
+}
+
+impl Default for GaussianNaiveBayes {
+    fn default() -> Self {
+        GaussianNaiveBayes::new()
+    }
+}
+
+impl GaussianNaiveBayes {
+    pub fn new() -> Self {
+        GaussianNaiveBayes { classes: Vec::new(), log_priors: Vec::new(), means: Vec::new(), variances: Vec::new() }
+    }
+
+    fn log_likelihoods(&self, row: &[f64]) -> Vec<f64> {
+        (0..self.classes.len())
+            .map(|c| {
+                self.log_priors[c] + row.iter()
+                    .zip(self.means[c].iter().zip(self.variances[c].iter()))
+                    .map(|(x, (mean, variance))| -0.5 * ((2.0 * std::f64::consts::PI * variance).ln() + (x - mean).powi(2) / variance))
+                    .sum::<f64>()
+            })
+            .collect()
+    }
+
+    // Апостеріорні ймовірності класів у порядку classes()
+    pub fn predict_proba(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        x.iter()
+            .map(|row| {
+                let scores = self.log_likelihoods(row);
+                let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                let exps: Vec<f64> = scores.iter().map(|s| (s - max).exp()).collect();
+                let sum: f64 = exps.iter().sum();
+                exps.iter().map(|e| e / sum).collect()
+            })
+            .collect()
+    }
+
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        if lines.next() != Some("gaussian_nb") {
+            return Err("очікувався заголовок gaussian_nb".into());
+        }
+        let mut model = GaussianNaiveBayes::new();
+        while let Some(line) = lines.next() {
+            let class = line.strip_prefix("class ").ok_or(format!("очікувався рядок class, отримано: {line}"))?;
+            model.classes.push(class.to_string());
+            model.log_priors.push(parse_values_line(lines.next(), "log_prior")?.first().copied().ok_or("порожній log_prior")?);
+            model.means.push(parse_values_line(lines.next(), "mean")?);
+            model.variances.push(parse_values_line(lines.next(), "variance")?);
+        }
+        Ok(model)
+    }
+}
+
+impl Classifier for GaussianNaiveBayes {
+    fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        let mut classes: Vec<String> = y.to_vec();
+        classes.sort();
+        classes.dedup();
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+
+        // Згладжування дисперсій: 1e-9 від найбільшої дисперсії ознак (як var_smoothing у sklearn)
+        let overall_variance = (0..dims)
+            .map(|d| {
+                let mean = x.iter().map(|row| row[d]).sum::<f64>() / x.len() as f64;
+                x.iter().map(|row| (row[d] - mean).powi(2)).sum::<f64>() / x.len() as f64
+            })
+            .fold(0.0, f64::max);
+        let epsilon = 1e-9 * overall_variance.max(1e-12);
+
+        self.log_priors.clear();
+        self.means.clear();
+        self.variances.clear();
+        for class in &classes {
+            let rows: Vec<&Vec<f64>> = x.iter().zip(y.iter()).filter(|(_, label)| *label == class).map(|(row, _)| row).collect();
+            let count = rows.len() as f64;
+            let means: Vec<f64> = (0..dims).map(|d| rows.iter().map(|row| row[d]).sum::<f64>() / count).collect();
+            let variances = (0..dims)
+                .map(|d| rows.iter().map(|row| (row[d] - means[d]).powi(2)).sum::<f64>() / count + epsilon)
+                .collect();
+            self.log_priors.push((count / y.len() as f64).ln());
+            self.means.push(means);
+            self.variances.push(variances);
+        }
+        self.classes = classes;
+    }
+
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        x.iter()
+            .map(|row| {
+                let scores = self.log_likelihoods(row);
+                let best = (0..scores.len()).max_by(|&a, &b| scores[a].partial_cmp(&scores[b]).unwrap_or(std::cmp::Ordering::Equal)).unwrap_or(0);
+                self.classes[best].clone()
+            })
+            .collect()
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = String::from("gaussian_nb\n");
+        for c in 0..self.classes.len() {
+            text.push_str(&format!("class {}\n{}\n{}\n{}\n", self.classes[c], values_line("log_prior", &[self.log_priors[c]]),
+                                   values_line("mean", &self.means[c]), values_line("variance", &self.variances[c])));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gaussian_model_separates_classes_and_round_trips() {
+        let x = vec![vec![1.0, 10.0], vec![1.2, 11.0], vec![0.8, 9.0], vec![5.0, 0.0], vec![5.2, 1.0], vec![4.8, -1.0]];
+        let y: Vec<String> = "a a a b b b".split(' ').map(|label| label.to_string()).collect();
+        let mut model = GaussianNaiveBayes::new();
+        Classifier::fit(&mut model, &x, &y);
+        assert_eq!(model.classes(), ["a", "b"]);
+        assert_eq!(model.predict(&[vec![1.1, 9.5], vec![4.9, 0.5]]), vec!["a", "b"]);
+        for proba in model.predict_proba(&x) {
+            assert!((proba.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+        // середина між класами за обома ознаками - рівні ймовірності
+        let middle = &model.predict_proba(&[vec![3.0, 5.0]])[0];
+        assert!((middle[0] - middle[1]).abs() < 1e-9);
+
+        let restored = GaussianNaiveBayes::from_text(&model.to_text()).unwrap();
+        assert_eq!(restored.predict_proba(&x), model.predict_proba(&x));
     }
 }
@@ -0,0 +1,451 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use crate::distance::DistanceMetric;
+
+// Алгоритм пошуку найближчих сусідів
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Algorithm {
+    // вибір за розміром та розмірністю даних
+    Auto,
+    BruteForce,
+    KdTree,
+    BallTree,
+}
+
+impl Algorithm {
+    pub fn from_name(name: &str) -> Option<Algorithm> {
+        match name {
+            "Auto" => Some(Algorithm::Auto),
+            "BruteForce" => Some(Algorithm::BruteForce),
+            "KdTree" => Some(Algorithm::KdTree),
+            "BallTree" => Some(Algorithm::BallTree),
+            _ => None,
+        }
+    }
+}
+
+const LEAF_SIZE: usize = 16;
+
+// Сусід: відстань та індекс навчального прикладу (під час пошуку в distance зберігається зменшена відстань).
+// Порядок - за відстанню, при рівності - за індексом, тому всі алгоритми повертають однакових сусідів.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Neighbor {
+    pub distance: f64,
+    pub index: usize,
+}
+
+impl Eq for Neighbor {}
+
+impl PartialOrd for Neighbor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Neighbor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.partial_cmp(&other.distance)
+            .unwrap_or(Ordering::Equal)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+// Куди обхід дерева складає знайдених кандидатів
+trait Candidates {
+    fn push(&mut self, candidate: Neighbor);
+    // Вузол з нижньою межею відстані bound може містити потрібного кандидата
+    fn admits(&self, bound: f64) -> bool;
+}
+
+// Невеликий допуск, щоб похибка округлення не відкинула сусіда з рівною відстанню
+fn within_tolerance(bound: f64, limit: f64) -> bool {
+    bound <= limit * (1.0 + 1e-12) + 1e-12
+}
+
+// k найкращих кандидатів (max-heap: на вершині найгірший із знайдених)
+struct TopK {
+    k: usize,
+    heap: BinaryHeap<Neighbor>,
+}
+
+impl TopK {
+    fn new(k: usize) -> Self {
+        TopK { k, heap: BinaryHeap::with_capacity(k + 1) }
+    }
+
+    fn into_sorted(self) -> Vec<Neighbor> {
+        self.heap.into_sorted_vec()
+    }
+}
+
+impl Candidates for TopK {
+    fn push(&mut self, candidate: Neighbor) {
+        if self.heap.len() < self.k {
+            self.heap.push(candidate);
+        } else if let Some(worst) = self.heap.peek() {
+            if candidate < *worst {
+                self.heap.pop();
+                self.heap.push(candidate);
+            }
+        }
+    }
+
+    fn admits(&self, bound: f64) -> bool {
+        self.heap.len() < self.k || self.heap.peek().is_some_and(|worst| within_tolerance(bound, worst.distance))
+    }
+}
+
+// Усі кандидати на (зменшеній) відстані не більше radius
+struct WithinRadius {
+    radius: f64,
+    found: Vec<Neighbor>,
+}
+
+impl Candidates for WithinRadius {
+    fn push(&mut self, candidate: Neighbor) {
+        if candidate.distance <= self.radius {
+            self.found.push(candidate);
+        }
+    }
+
+    fn admits(&self, bound: f64) -> bool {
+        within_tolerance(bound, self.radius)
+    }
+}
+
+// Навчальні точки одним суцільним блоком пам'яті (рядок за рядком)
+struct FlatMatrix {
+    data: Vec<f64>,
+    dims: usize,
+}
+
+impl FlatMatrix {
+    fn from_rows(rows: &[Vec<f64>]) -> Self {
+        let dims = rows.first().map(|row| row.len()).unwrap_or(0);
+        let mut data = Vec::with_capacity(rows.len() * dims);
+        for row in rows {
+            data.extend_from_slice(row);
+        }
+        FlatMatrix { data, dims }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len().checked_div(self.dims).unwrap_or(0)
+    }
+
+    fn row(&self, i: usize) -> &[f64] {
+        &self.data[i * self.dims..(i + 1) * self.dims]
+    }
+
+    fn value(&self, i: usize, d: usize) -> f64 {
+        self.data[i * self.dims + d]
+    }
+}
+
+pub struct KdNode {
+    start: usize,
+    end: usize,
+    // (вимір, значення поділу, лівий, правий) для внутрішніх вузлів
+    split: Option<(usize, f64, usize, usize)>,
+    // мінімальні та максимальні координати точок вузла
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+}
+
+pub struct BallNode {
+    start: usize,
+    end: usize,
+    children: Option<(usize, usize)>,
+    // центр - одна з точок вузла, тому межа коректна для будь-якої метрики
+    center: usize,
+    radius: f64,
+}
+
+enum Backend {
+    BruteForce,
+    KdTree {
+        order: Vec<usize>,
+        nodes: Vec<KdNode>,
+    },
+    BallTree {
+        order: Vec<usize>,
+        nodes: Vec<BallNode>,
+    },
+}
+
+pub struct NeighborIndex {
+    points: FlatMatrix,
+    metric: DistanceMetric,
+    backend: Backend,
+}
+
+// Вимір з найбільшим розкидом значень серед order[start..end]
+fn widest_dimension(points: &FlatMatrix, order: &[usize]) -> (usize, Vec<f64>, Vec<f64>) {
+    let mut lower = vec![f64::INFINITY; points.dims];
+    let mut upper = vec![f64::NEG_INFINITY; points.dims];
+    for &i in order {
+        for (d, value) in points.row(i).iter().enumerate() {
+            lower[d] = lower[d].min(*value);
+            upper[d] = upper[d].max(*value);
+        }
+    }
+    let widest = (0..points.dims)
+        .max_by(|&a, &b| (upper[a] - lower[a]).partial_cmp(&(upper[b] - lower[b])).unwrap_or(Ordering::Equal))
+        .unwrap_or(0);
+    (widest, lower, upper)
+}
+
+// Поділ order по медіані виміру dim
+fn partition_by_median(points: &FlatMatrix, order: &mut [usize], dim: usize) -> (usize, f64) {
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by(mid, |&a, &b| {
+        points.value(a, dim).partial_cmp(&points.value(b, dim)).unwrap_or(Ordering::Equal)
+    });
+    (mid, points.value(order[mid], dim))
+}
+
+fn build_kd(points: &FlatMatrix, order: &mut [usize], offset: usize, nodes: &mut Vec<KdNode>) -> usize {
+    let (dim, lower, upper) = widest_dimension(points, order);
+    let id = nodes.len();
+    nodes.push(KdNode { start: offset, end: offset + order.len(), split: None, lower, upper });
+    if order.len() > LEAF_SIZE && nodes[id].upper[dim] > nodes[id].lower[dim] {
+        let (mid, value) = partition_by_median(points, order, dim);
+        let (left_part, right_part) = order.split_at_mut(mid);
+        let left = build_kd(points, left_part, offset, nodes);
+        let right = build_kd(points, right_part, offset + mid, nodes);
+        nodes[id].split = Some((dim, value, left, right));
+    }
+    id
+}
+
+fn build_ball(points: &FlatMatrix, metric: &DistanceMetric, order: &mut [usize], offset: usize, nodes: &mut Vec<BallNode>) -> usize {
+    // центр - точка, найближча до покоординатного середнього
+    let mut mean = vec![0.0; points.dims];
+    for &i in order.iter() {
+        for (m, value) in mean.iter_mut().zip(points.row(i)) {
+            *m += value / order.len() as f64;
+        }
+    }
+    let squared_to_mean = |i: usize| points.row(i).iter().zip(mean.iter()).map(|(x, m)| (x - m) * (x - m)).sum::<f64>();
+    let center = order.iter()
+        .copied()
+        .min_by(|&a, &b| squared_to_mean(a).partial_cmp(&squared_to_mean(b)).unwrap_or(Ordering::Equal))
+        .unwrap();
+    let radius = order.iter().map(|&i| metric.distance(points.row(i), points.row(center))).fold(0.0, f64::max);
+    let id = nodes.len();
+    nodes.push(BallNode { start: offset, end: offset + order.len(), children: None, center, radius });
+    if order.len() > LEAF_SIZE && radius > 0.0 {
+        let (dim, _, _) = widest_dimension(points, order);
+        let (mid, _) = partition_by_median(points, order, dim);
+        let (left_part, right_part) = order.split_at_mut(mid);
+        let left = build_ball(points, metric, left_part, offset, nodes);
+        let right = build_ball(points, metric, right_part, offset + mid, nodes);
+        nodes[id].children = Some((left, right));
+    }
+    id
+}
+
+impl NeighborIndex {
+    pub fn build(points: &[Vec<f64>], metric: DistanceMetric, algorithm: Algorithm) -> Self {
+        let points = FlatMatrix::from_rows(points);
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        let backend = match Self::resolve(algorithm, points.len(), points.dims, &metric) {
+            Algorithm::KdTree => {
+                let mut nodes = Vec::new();
+                build_kd(&points, &mut order, 0, &mut nodes);
+                Backend::KdTree { order, nodes }
+            }
+            Algorithm::BallTree => {
+                let mut nodes = Vec::new();
+                build_ball(&points, &metric, &mut order, 0, &mut nodes);
+                Backend::BallTree { order, nodes }
+            }
+            _ => Backend::BruteForce,
+        };
+        NeighborIndex { points, metric, backend }
+    }
+
+    // Auto: для малих вибірок дерево не окупається; KD-дерево ефективне при невеликій розмірності,
+    // ball tree краще тримається при більшій; при дуже великій розмірності - повний перебір.
+    // KD-дерево можливе лише для метрик Мінковського, ball tree - для справжніх метрик, тому явно
+    // заданий алгоритм теж може бути замінений: KdTree -> BallTree для інших метрик, будь-яке дерево ->
+    // BruteForce для Cosine, HEOM і HVDM. Фактичний алгоритм повертає algorithm().
+    pub fn resolve(algorithm: Algorithm, n: usize, dims: usize, metric: &DistanceMetric) -> Algorithm {
+        let kind = metric.kind();
+        if n == 0 || !kind.is_metric() {
+            return Algorithm::BruteForce;
+        }
+        match algorithm {
+            Algorithm::KdTree if !kind.is_minkowski() => Algorithm::BallTree,
+            Algorithm::Auto => {
+                if n < 4 * LEAF_SIZE || dims > 40 {
+                    Algorithm::BruteForce
+                } else if dims <= 10 && kind.is_minkowski() {
+                    Algorithm::KdTree
+                } else {
+                    Algorithm::BallTree
+                }
+            }
+            other => other,
+        }
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        match self.backend {
+            Backend::BruteForce => Algorithm::BruteForce,
+            Backend::KdTree { .. } => Algorithm::KdTree,
+            Backend::BallTree { .. } => Algorithm::BallTree,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    // Навчальна точка i (у вихідному порядку)
+    pub fn point(&self, i: usize) -> &[f64] {
+        self.points.row(i)
+    }
+
+    pub fn metric(&self) -> &DistanceMetric {
+        &self.metric
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // k найближчих сусідів, від найближчого
+    pub fn k_nearest(&self, query: &[f64], k: usize) -> Vec<Neighbor> {
+        let mut top = TopK::new(k.min(self.len()));
+        if top.k == 0 {
+            return Vec::new();
+        }
+        self.search(query, &mut top);
+        self.to_distances(top.into_sorted())
+    }
+
+    // Усі навчальні приклади на відстані не більше radius, від найближчого
+    pub fn within_radius(&self, query: &[f64], radius: f64) -> Vec<Neighbor> {
+        let mut candidates = WithinRadius { radius: self.metric.distance_to_reduced(radius), found: Vec::new() };
+        self.search(query, &mut candidates);
+        candidates.found.sort();
+        self.to_distances(candidates.found)
+    }
+
+    fn search(&self, query: &[f64], candidates: &mut impl Candidates) {
+        match &self.backend {
+            Backend::BruteForce => {
+                for index in 0..self.points.len() {
+                    candidates.push(Neighbor { distance: self.metric.reduced_distance(self.points.row(index), query), index });
+                }
+            }
+            Backend::KdTree { order, nodes } => self.search_kd(order, nodes, 0, query, candidates),
+            Backend::BallTree { order, nodes } => self.search_ball(order, nodes, 0, query, candidates),
+        }
+    }
+
+    // корінь/степінь рахуються лише для знайдених сусідів
+    fn to_distances(&self, mut neighbors: Vec<Neighbor>) -> Vec<Neighbor> {
+        for neighbor in neighbors.iter_mut() {
+            neighbor.distance = self.metric.reduced_to_distance(neighbor.distance);
+        }
+        neighbors
+    }
+
+    fn scan(&self, indices: &[usize], query: &[f64], candidates: &mut impl Candidates) {
+        for &index in indices {
+            candidates.push(Neighbor { distance: self.metric.reduced_distance(self.points.row(index), query), index });
+        }
+    }
+
+    fn search_kd(&self, order: &[usize], nodes: &[KdNode], id: usize, query: &[f64], candidates: &mut impl Candidates) {
+        let node = &nodes[id];
+        if !candidates.admits(self.metric.min_reduced_distance_to_box(query, &node.lower, &node.upper)) {
+            return;
+        }
+        match node.split {
+            None => self.scan(&order[node.start..node.end], query, candidates),
+            Some((dim, value, left, right)) => {
+                // спершу гілка, в яку потрапляє запит
+                let (near, far) = if query[dim] < value { (left, right) } else { (right, left) };
+                self.search_kd(order, nodes, near, query, candidates);
+                self.search_kd(order, nodes, far, query, candidates);
+            }
+        }
+    }
+
+    fn search_ball(&self, order: &[usize], nodes: &[BallNode], id: usize, query: &[f64], candidates: &mut impl Candidates) {
+        let node = &nodes[id];
+        // межа за нерівністю трикутника рахується у справжніх відстанях, порівнюється - у зменшених
+        let bound = (self.metric.distance(query, self.points.row(node.center)) - node.radius).max(0.0);
+        if !candidates.admits(self.metric.distance_to_reduced(bound)) {
+            return;
+        }
+        match node.children {
+            None => self.scan(&order[node.start..node.end], query, candidates),
+            Some((left, right)) => {
+                let left_dist = self.metric.reduced_distance(query, self.points.row(nodes[left].center));
+                let right_dist = self.metric.reduced_distance(query, self.points.row(nodes[right].center));
+                let (near, far) = if left_dist <= right_dist { (left, right) } else { (right, left) };
+                self.search_ball(order, nodes, near, query, candidates);
+                self.search_ball(order, nodes, far, query, candidates);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::Distance;
+    use crate::util;
+
+    fn assert_same_neighbors(expected: &[Neighbor], actual: &[Neighbor], context: &str) {
+        assert_eq!(expected.len(), actual.len(), "{context}: кількість сусідів");
+        for (e, a) in expected.iter().zip(actual) {
+            assert_eq!(e.index, a.index, "{context}");
+            assert!((e.distance - a.distance).abs() <= 1e-9 * e.distance.max(1.0), "{context}: {} != {}", e.distance, a.distance);
+        }
+    }
+
+    // Дерева мають повертати тих самих сусідів (і в тому самому порядку), що й повний перебір
+    #[test]
+    fn trees_match_brute_force() {
+        for (path, skip_columns) in [("datasets/iris.csv", 1), ("datasets/vehicle.csv", 0)] {
+            let (x, y, _) = util::load_numeric_dataset(path, skip_columns).unwrap();
+            let (train, test) = util::train_test_split_indices(&y, 0.3, 3);
+            let x_train = util::select_rows(&x, &train);
+            let x_test = util::select_rows(&x, &test);
+
+            for distance in [Distance::Euclidean, Distance::Manhattan, Distance::Mahalanobis] {
+                let metric = DistanceMetric::fit(distance, &x_train, &[], None);
+                let brute_force = NeighborIndex::build(&x_train, metric.clone(), Algorithm::BruteForce);
+                for algorithm in [Algorithm::KdTree, Algorithm::BallTree] {
+                    let index = NeighborIndex::build(&x_train, metric.clone(), algorithm);
+                    // KD-дерево для метрики не з сімейства Мінковського замінюється на ball tree
+                    let expected_algorithm = if distance.is_minkowski() { algorithm } else { Algorithm::BallTree };
+                    assert_eq!(index.algorithm(), expected_algorithm);
+                    for query in &x_test {
+                        let context = format!("{path} {distance:?} {algorithm:?}");
+                        let expected = brute_force.k_nearest(query, 7);
+                        assert_same_neighbors(&expected, &index.k_nearest(query, 7), &context);
+                        let radius = expected[3].distance;
+                        assert_same_neighbors(&brute_force.within_radius(query, radius), &index.within_radius(query, radius), &context);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn non_metric_distances_fall_back_to_brute_force() {
+        let points: Vec<Vec<f64>> = (0..200).map(|i| vec![i as f64, (i % 7) as f64]).collect();
+        for distance in [Distance::Cosine, Distance::Heom, Distance::Hvdm] {
+            let classes: Vec<usize> = (0..200).map(|i| i % 2).collect();
+            let metric = DistanceMetric::fit(distance, &points, &[false, true], Some(&classes));
+            let index = NeighborIndex::build(&points, metric, Algorithm::BallTree);
+            assert_eq!(index.algorithm(), Algorithm::BruteForce, "{distance:?}");
+        }
+    }
+}
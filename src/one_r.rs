@@ -1,9 +1,42 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use crate::decision_list::{print_decision_list, Condition, DecisionRule};
+use crate::discretize::{bin_index, interval_label, weighted_one_r_cut_points};
+use crate::dummy::ZeroRClassifier;
+
+// Правило "значення ознаки -> клас" з покриттям (сумарна вага прикладів з цим значенням)
+#[derive(Clone, Debug)]
+pub struct Rule {
+    pub value: String,
+    pub class: String,
+    pub coverage: f64,
+    pub correct: f64,
+}
+
+// Ключ правила: номер інтервалу числової ознаки, значення категоріальної або пропуск.
+// Порядок варіантів задає порядок правил для виводу: інтервали за зростанням, категорії за алфавітом, пропуск - в кінці
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum RuleKey {
+    Bin(usize),
+    Value(String),
+    Missing,
+}
 
 pub struct OneRClassifier {
     best_feature: usize,
-    rules: HashMap<String, String>,
+    rules: HashMap<RuleKey, String>,
     default_class: String,
+    min_bucket_size: usize,
+    forced_feature: Option<usize>,
+    cut_points: Vec<Option<Vec<f64>>>,  // межі інтервалів числових ознак (None - категоріальна ознака)
+    feature_rules: Vec<Vec<Rule>>,  // правила кожної ознаки-кандидата
+    feature_errors: Vec<f64>,  // частка помилок правил кожної ознаки на навчальній вибірці
+}
+
+impl Default for OneRClassifier {
+    fn default() -> Self {
+        OneRClassifier::new()
+    }
 }
 
 impl OneRClassifier {
@@ -12,67 +45,127 @@ impl OneRClassifier {
             best_feature: 0,
             rules: HashMap::new(),
             default_class: String::new(),
+            min_bucket_size: 6,
+            forced_feature: None,
+            cut_points: Vec::new(),
+            feature_rules: Vec::new(),
+            feature_errors: Vec::new(),
         }
     }
 
-    pub fn fit(&mut self, x: &Vec<Vec<String>>, y: &Vec<String>) {
-        let num_features = x[0].len();
-        let mut best_error = usize::MAX;
-        let mut best_feature_index = 0;
-        let mut best_rules: HashMap<String, String> = HashMap::new();
+    // Використовувати задану ознаку замість ознаки з найменшою помилкою (правила й помилки решти ознак усе одно рахуються)
+    pub fn with_forced_feature(mut self, feature: usize) -> Self {
+        self.forced_feature = Some(feature);
+        self
+    }
+
+    // Мінімальна кількість прикладів мажоритарного класу в інтервалі числової ознаки (SMALL у Holte, 1993);
+    // 0 - числові ознаки розглядаються як категоріальні
+    pub fn with_min_bucket_size(mut self, min_bucket_size: usize) -> Self {
+        self.min_bucket_size = min_bucket_size;
+        self
+    }
 
-        let mut class_counts: HashMap<&String, usize> = HashMap::new();
-        for label in y {
-            *class_counts.entry(label).or_insert(0) += 1;
+    // Значення ознаки для правил: для числової ознаки - номер інтервалу (а не мітка, яка могла б збігтися
+    // для близьких меж)
+    fn rule_key(&self, feature: usize, value: &str) -> RuleKey {
+        match self.cut_points.get(feature) {
+            Some(Some(cuts)) => match value.trim().parse::<f64>() {
+                Ok(number) if !number.is_nan() => RuleKey::Bin(bin_index(cuts, number)),
+                _ => RuleKey::Missing,
+            },
+            _ if value.is_empty() => RuleKey::Missing,
+            _ => RuleKey::Value(value.to_string()),
         }
-        if let Some((majority_class, _)) = class_counts.iter().max_by_key(|entry| entry.1) {
-            self.default_class = (*majority_class).clone();
+    }
+
+    // Значення правила для виводу: мітка інтервалу, категорія або порожній рядок для пропуску
+    fn rule_label(&self, feature: usize, key: &RuleKey) -> String {
+        match (key, self.cut_points.get(feature)) {
+            (RuleKey::Bin(bin), Some(Some(cuts))) => interval_label(cuts, *bin),
+            (RuleKey::Value(value), _) => value.clone(),
+            _ => String::new(),
         }
+    }
+
+    pub fn fit(&mut self, x: &[Vec<String>], y: &[String]) {
+        self.fit_weighted(x, y, &vec![1.0; y.len()]);
+    }
+
+    // Навчання з вагами прикладів: помилка правила - сума ваг неправильно класифікованих прикладів
+    pub fn fit_weighted(&mut self, x: &[Vec<String>], y: &[String], sample_weight: &[f64]) {
+        let num_features = x[0].len();
+        let total_weight: f64 = sample_weight.iter().sum();
+
+        // Для значень, яких не було в навчальній вибірці - відповідь ZeroR
+        let mut zero_r = ZeroRClassifier::new();
+        zero_r.fit_weighted(y, sample_weight);
+        self.default_class = zero_r.default_class().to_string();
 
+        // Числова ознака (усі непорожні значення - числа) ділиться на інтервали за алгоритмом Holte
+        // (приклади в інтервалах рахуються з вагами)
+        self.cut_points = (0..num_features)
+            .map(|fi| {
+                let values: Option<Vec<f64>> = x.iter()
+                    .map(|row| if row[fi].trim().is_empty() { Some(f64::NAN) } else { row[fi].trim().parse::<f64>().ok() })
+                    .collect();
+                match values {
+                    Some(values) if self.min_bucket_size > 0 && values.iter().any(|v| !v.is_nan()) => {
+                        Some(weighted_one_r_cut_points(&values, y, sample_weight, self.min_bucket_size))
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+
+        self.feature_rules.clear();
+        self.feature_errors.clear();
+        let mut feature_keys: Vec<Vec<RuleKey>> = Vec::new();
         for fi in 0..num_features {
-            let mut value_class_counts: HashMap<String, HashMap<String, usize>> = HashMap::new();
-            for (index, (row, label)) in x.iter().zip(y.iter()).enumerate() {
-                let value = &row[fi];
-                value_class_counts
-                    .entry(value.clone())
-                    .or_insert(HashMap::new())
+            let mut value_class_counts: HashMap<RuleKey, HashMap<String, f64>> = HashMap::new();
+            for ((row, label), &weight) in x.iter().zip(y.iter()).zip(sample_weight) {
+                *value_class_counts
+                    .entry(self.rule_key(fi, &row[fi]))
+                    .or_default()
                     .entry(label.clone())
-                    .or_insert(0_usize);
-                *value_class_counts.get_mut(value).unwrap().get_mut(label).unwrap() += 1;
-                let count = value_class_counts.get(value).unwrap().get(label).unwrap();
+                    .or_default() += weight;
             }
 
-            let mut rules: HashMap<String, String> = HashMap::new();
-            let mut errors = 0;
-            for (value, class_map) in &value_class_counts {
-                let majority_class = class_map
+            let mut keyed_rules: Vec<(RuleKey, Rule)> = Vec::new();
+            let mut errors = 0.0;
+            for (key, class_map) in value_class_counts {
+                // при рівності - лексикографічно менший клас, щоб правила не залежали від порядку HashMap
+                let (majority_class, correct) = class_map
                     .iter()
-                    .max_by_key(|entry| entry.1)
-                    .map(|(class, _)| class.clone())
+                    .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal).then_with(|| b.0.cmp(a.0)))
+                    .map(|(class, weight)| (class.clone(), *weight))
                     .unwrap();
-                rules.insert(value.clone(), majority_class.clone());
-
-                let total_for_value: usize = class_map.values().sum();
-                let correct_count = class_map.get(&majority_class).unwrap();
-                errors += total_for_value - *correct_count;
-            }
-
-            if errors < best_error {
-                best_error = errors;
-                best_feature_index = fi;
-                best_rules = rules;
+                let coverage: f64 = class_map.values().sum();
+                errors += coverage - correct;
+                let rule = Rule { value: self.rule_label(fi, &key), class: majority_class, coverage, correct };
+                keyed_rules.push((key, rule));
             }
+            keyed_rules.sort_by(|a, b| a.0.cmp(&b.0));
+            feature_keys.push(keyed_rules.iter().map(|(key, _)| key.clone()).collect());
+            self.feature_rules.push(keyed_rules.into_iter().map(|(_, rule)| rule).collect());
+            self.feature_errors.push(if total_weight > 0.0 { errors / total_weight } else { 0.0 });
         }
 
-        self.best_feature = best_feature_index;
-        self.rules = best_rules;
+        // Перша ознака з найменшою помилкою
+        let best_feature_index = (0..num_features)
+            .fold(0, |best, fi| if self.feature_errors[fi] < self.feature_errors[best] { fi } else { best });
+        self.best_feature = self.forced_feature.filter(|&fi| fi < num_features).unwrap_or(best_feature_index);
+        self.rules = feature_keys.swap_remove(self.best_feature).into_iter()
+            .zip(self.feature_rules[self.best_feature].iter())
+            .map(|(key, rule)| (key, rule.class.clone()))
+            .collect();
     }
 
     pub fn predict(&self, data: &[Vec<String>]) -> Vec<String> {
         let mut predictions: Vec<String> = Vec::new();
         for features in data {
             if let Some(value) = features.get(self.best_feature) {
-                let predicted = self.rules.get(value)
+                let predicted = self.rules.get(&self.rule_key(self.best_feature, value))
                                    .unwrap_or(&self.default_class)
                                    .clone();
                 predictions.push(predicted);
@@ -86,4 +179,110 @@ impl OneRClassifier {
     pub fn get_best_feature_index(&self) -> usize {
         self.best_feature
     }
+
+    // Правила обраної ознаки
+    pub fn rules(&self) -> &[Rule] {
+        &self.feature_rules[self.best_feature]
+    }
+
+    // Правила, які мала б будь-яка ознака-кандидат
+    pub fn feature_rules(&self, feature: usize) -> &[Rule] {
+        &self.feature_rules[feature]
+    }
+
+    pub fn feature_errors(&self) -> &[f64] {
+        &self.feature_errors
+    }
+
+    pub fn default_class(&self) -> &str {
+        &self.default_class
+    }
+
+    // Правила обраної ознаки як список правил з однією умовою
+    pub fn decision_rules(&self) -> Vec<DecisionRule> {
+        self.rules().iter()
+            .map(|rule| DecisionRule {
+                conditions: vec![Condition { feature: self.best_feature, value: rule.value.clone() }],
+                class: rule.class.clone(),
+                coverage: rule.coverage,
+                correct: rule.correct,
+            })
+            .collect()
+    }
+
+    pub fn print_rules(&self, feature_names: &[&str]) {
+        print_decision_list(&self.decision_rules(), &self.default_class, feature_names);
+    }
+
+    // Таблиця помилок усіх ознак-кандидатів, від найкращої
+    pub fn print_error_table(&self, feature_names: &[&str]) {
+        let mut order: Vec<usize> = (0..self.feature_errors.len()).collect();
+        order.sort_by(|&a, &b| self.feature_errors[a].partial_cmp(&self.feature_errors[b]).unwrap_or(Ordering::Equal));
+        for fi in order {
+            let marker = if fi == self.best_feature { "*" } else { " " };
+            println!("{marker} {:<30} error {:.4} ({} rules)", feature_names[fi], self.feature_errors[fi], self.feature_rules[fi].len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(values: &[&str]) -> Vec<Vec<String>> {
+        values.iter().map(|row| row.split_whitespace().map(|value| value.to_string()).collect()).collect()
+    }
+
+    #[test]
+    fn weights_decide_the_best_feature() {
+        // без ваг перша ознака помиляється двічі, друга - один раз (на останньому прикладі)
+        let x = rows(&["a p", "a p", "b q", "b q", "a q", "b p", "a q"]);
+        let y: Vec<String> = "yes yes no no no yes yes".split_whitespace().map(|class| class.to_string()).collect();
+        let mut model = OneRClassifier::new();
+        model.fit(&x, &y);
+        assert_eq!(model.get_best_feature_index(), 1);
+        model.fit_weighted(&x, &y, &[1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 5.0]);
+        assert_eq!(model.get_best_feature_index(), 0);
+        assert_eq!(model.predict(&rows(&["a q", "b p"])), vec!["yes", "no"]);
+    }
+
+    // Межі 1.0000075, 1.00002 і 1.0000325 близькі, але кожен інтервал має власне правило
+    #[test]
+    fn close_cut_points_keep_separate_rules() {
+        let x = rows(&["1", "1", "1", "1.000015", "1.000015", "1.000015", "1.000025", "1.000025", "1.000025",
+                       "1.00004", "1.00004", "1.00004"]);
+        let y: Vec<String> = "a a a b b b a a a b b b".split_whitespace().map(|class| class.to_string()).collect();
+        let mut model = OneRClassifier::new().with_min_bucket_size(3);
+        model.fit(&x, &y);
+        assert_eq!(model.predict(&x), y);
+        let rules: Vec<(&str, &str)> = model.rules().iter().map(|rule| (rule.value.as_str(), rule.class.as_str())).collect();
+        assert_eq!(rules, vec![("<=1.00001", "a"), ("(1.00001, 1.00002]", "b"), ("(1.00002, 1.00003]", "a"), (">1.00003", "b")]);
+    }
+
+    // Номінальний weather з Witten & Frank: помилки 4/14, 5/14, 4/14, 5/14, обирається Outlook
+    #[test]
+    fn weather_error_table_and_forced_feature() {
+        let x = rows(&["sunny hot high false", "sunny hot high true", "overcast hot high false", "rainy mild high false",
+                       "rainy cool normal false", "rainy cool normal true", "overcast cool normal true", "sunny mild high false",
+                       "sunny cool normal false", "rainy mild normal false", "sunny mild normal true", "overcast mild high true",
+                       "overcast hot normal false", "rainy mild high true"]);
+        let y: Vec<String> = "no no yes yes yes no yes no yes yes yes yes yes no".split_whitespace().map(|class| class.to_string()).collect();
+        let mut model = OneRClassifier::new();
+        model.fit(&x, &y);
+        let expected = [4.0, 5.0, 4.0, 5.0];
+        assert!(model.feature_errors().iter().zip(expected).all(|(error, count)| (error - count / 14.0).abs() < 1e-12));
+        assert_eq!(model.get_best_feature_index(), 0);
+        let rules: Vec<(&str, &str, f64)> = model.rules().iter().map(|rule| (rule.value.as_str(), rule.class.as_str(), rule.correct)).collect();
+        assert_eq!(rules, vec![("overcast", "yes", 4.0), ("rainy", "yes", 3.0), ("sunny", "no", 3.0)]);
+        assert_eq!(model.default_class(), "yes");
+
+        // примусова ознака Windy: при рівності 3/3 для true - лексикографічно менший клас
+        let mut windy = OneRClassifier::new().with_forced_feature(3);
+        windy.fit(&x, &y);
+        assert_eq!(windy.get_best_feature_index(), 3);
+        assert_eq!(windy.feature_errors(), model.feature_errors());
+        let rules: Vec<(&str, &str)> = windy.rules().iter().map(|rule| (rule.value.as_str(), rule.class.as_str())).collect();
+        assert_eq!(rules, vec![("false", "yes"), ("true", "no")]);
+        assert_eq!(windy.predict(&rows(&["sunny hot high true"])), vec!["no"]);
+    }
 }
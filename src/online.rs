@@ -0,0 +1,243 @@
+use std::collections::{HashMap, HashSet};
+use crate::naive_bayes::tokenize;
+
+// Правило оновлення ваг онлайн-класифікатора
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OnlineAlgorithm {
+    // w += y x при помилці (Rosenblatt, 1958)
+    Perceptron,
+    // той самий перцептрон, але передбачення за середніми вагами всіх кроків (Freund & Schapire, 1999)
+    AveragedPerceptron,
+    // PA-I (Crammer et al., 2006): найменша зміна ваг, що дає зазор 1, з обмеженням кроку C
+    PassiveAggressive(f64),
+    // Winnow2 (Littlestone, 1988) з порогом за довжиною: додатні ваги, множення на promotion / ділення при помилці.
+    // На відміну від сталого порогу θ в оригіналі, поріг дорівнює кількості слів повідомлення
+    // (середня вага слова має перевищити 1), бо словник не відомий наперед і росте з даними
+    Winnow(f64),
+}
+
+// Бінарний онлайн-класифікатор текстів: ознаки - наявність слів (розріджено, словник росте з даними),
+// навчання по одному повідомленню через partial_fit, тож модель можна дооновлювати в будь-який момент.
+// Позитивний клас - positive_class (наприклад, "Spam"), решта міток - негативний.
+pub struct OnlineClassifier {
+    algorithm: OnlineAlgorithm,
+    positive_class: String,
+    epochs: usize,
+    weights: HashMap<String, f64>,
+    bias: f64,
+    // для усереднення: сума (t - 1) * y * x за всі оновлення на t-му повідомленні, тож середнє ваг після
+    // кожного з n повідомлень дорівнює w - accumulated / n
+    accumulated: HashMap<String, f64>,
+    accumulated_bias: f64,
+    seen: usize,
+    mistakes: usize,
+}
+
+impl OnlineClassifier {
+    pub fn new(algorithm: OnlineAlgorithm, positive_class: &str) -> Self {
+        OnlineClassifier {
+            algorithm,
+            positive_class: positive_class.to_string(),
+            epochs: 1,
+            weights: HashMap::new(),
+            bias: 0.0,
+            accumulated: HashMap::new(),
+            accumulated_bias: 0.0,
+            seen: 0,
+            mistakes: 0,
+        }
+    }
+
+    // Скільки разів fit проходить по повідомленнях
+    pub fn with_epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs.max(1);
+        self
+    }
+
+    // Вага слова, якого модель ще не бачила: 1 для Winnow, 0 для решти
+    fn weight(&self, token: &str) -> f64 {
+        let default = if matches!(self.algorithm, OnlineAlgorithm::Winnow(_)) { 1.0 } else { 0.0 };
+        self.weights.get(token).copied().unwrap_or(default)
+    }
+
+    // Оцінка за поточними вагами (без усереднення); > 0 - позитивний клас
+    fn raw_score(&self, tokens: &HashSet<&str>) -> f64 {
+        match self.algorithm {
+            OnlineAlgorithm::Winnow(_) => {
+                if tokens.is_empty() {
+                    return -1.0;
+                }
+                tokens.iter().map(|token| self.weight(token)).sum::<f64>() / tokens.len() as f64 - 1.0
+            }
+            _ => self.bias + tokens.iter().map(|token| self.weight(token)).sum::<f64>(),
+        }
+    }
+
+    fn learn(&mut self, tokens: &HashSet<&str>, is_positive: bool) {
+        let y = if is_positive { 1.0 } else { -1.0 };
+        let score = self.raw_score(tokens);
+        self.seen += 1;
+        if y * score <= 0.0 {
+            self.mistakes += 1;
+        }
+        match self.algorithm {
+            OnlineAlgorithm::Perceptron | OnlineAlgorithm::AveragedPerceptron => {
+                if y * score <= 0.0 {
+                    let step = (self.seen - 1) as f64 * y;
+                    for &token in tokens {
+                        *self.weights.entry(token.to_string()).or_default() += y;
+                        *self.accumulated.entry(token.to_string()).or_default() += step;
+                    }
+                    self.bias += y;
+                    self.accumulated_bias += step;
+                }
+            }
+            OnlineAlgorithm::PassiveAggressive(c) => {
+                let loss = (1.0 - y * score).max(0.0);
+                if loss > 0.0 {
+                    // |x|^2 - кількість слів плюс стала ознака зсуву
+                    let tau = (loss / (tokens.len() + 1) as f64).min(c);
+                    for &token in tokens {
+                        *self.weights.entry(token.to_string()).or_default() += tau * y;
+                    }
+                    self.bias += tau * y;
+                }
+            }
+            OnlineAlgorithm::Winnow(promotion) => {
+                if y * score <= 0.0 {
+                    let factor = if is_positive { promotion } else { 1.0 / promotion };
+                    for &token in tokens {
+                        let weight = self.weight(token) * factor;
+                        self.weights.insert(token.to_string(), weight);
+                    }
+                }
+            }
+        }
+    }
+
+    // Одне нове повідомлення з міткою
+    pub fn partial_fit(&mut self, text: &str, label: &str) {
+        let lower_case_text = text.to_lowercase();
+        self.learn(&tokenize(&lower_case_text), label == self.positive_class);
+    }
+
+    // epochs проходів по повідомленнях у заданому порядку (продовжує навчання, а не починає заново)
+    pub fn fit(&mut self, x: &[String], y: &[String]) {
+        for _ in 0..self.epochs {
+            for (text, label) in x.iter().zip(y) {
+                self.partial_fit(text, label);
+            }
+        }
+    }
+
+    // Відстань до розділяючої площини (> 0 - позитивний клас); для усередненого перцептрона - за середніми вагами
+    pub fn decision_function(&self, text: &str) -> f64 {
+        let lower_case_text = text.to_lowercase();
+        let tokens = tokenize(&lower_case_text);
+        match self.algorithm {
+            OnlineAlgorithm::AveragedPerceptron if self.seen > 0 => {
+                let c = self.seen as f64;
+                let averaged = |token: &str| self.weight(token) - self.accumulated.get(token).copied().unwrap_or(0.0) / c;
+                self.bias - self.accumulated_bias / c + tokens.iter().map(|token| averaged(token)).sum::<f64>()
+            }
+            _ => self.raw_score(&tokens),
+        }
+    }
+
+    pub fn predict(&self, text: &str) -> bool {
+        self.decision_function(text) > 0.0
+    }
+
+    // Кількість побачених повідомлень і помилок на них до оновлення (онлайн-помилка)
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    pub fn mistakes(&self) -> usize {
+        self.mistakes
+    }
+
+    // Слова з найбільшими за модулем вагами (для Winnow - найбільшими)
+    pub fn top_tokens(&self, count: usize) -> Vec<(&str, f64)> {
+        let mut tokens: Vec<(&str, f64)> = self.weights.iter().map(|(token, &weight)| (token.as_str(), weight)).collect();
+        let key = |weight: f64| if matches!(self.algorithm, OnlineAlgorithm::Winnow(_)) { weight } else { weight.abs() };
+        tokens.sort_by(|a, b| key(b.1).total_cmp(&key(a.1)).then_with(|| a.0.cmp(b.0)));
+        tokens.truncate(count);
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGES: [(&str, &str); 6] = [
+        ("win money now", "Spam"),
+        ("meeting at noon", "Ham"),
+        ("free money offer", "Spam"),
+        ("lunch at noon tomorrow", "Ham"),
+        ("win a free prize", "Spam"),
+        ("money for lunch", "Ham"),
+    ];
+
+    // Середні ваги w - accumulated / n збігаються з прямим середнім ваг звичайного перцептрона після кожного повідомлення
+    #[test]
+    fn averaged_weights_match_running_average() {
+        let mut averaged = OnlineClassifier::new(OnlineAlgorithm::AveragedPerceptron, "Spam");
+        let mut plain = OnlineClassifier::new(OnlineAlgorithm::Perceptron, "Spam");
+        let mut weight_sums: HashMap<String, f64> = HashMap::new();
+        let mut bias_sum = 0.0;
+        for _ in 0..3 {
+            for (text, label) in MESSAGES {
+                averaged.partial_fit(text, label);
+                plain.partial_fit(text, label);
+                for (token, weight) in &plain.weights {
+                    *weight_sums.entry(token.clone()).or_default() += weight;
+                }
+                bias_sum += plain.bias;
+            }
+        }
+        let n = averaged.seen() as f64;
+        assert_eq!(averaged.mistakes(), plain.mistakes());
+        for text in ["free money", "noon meeting", "win lunch", "unknown words"] {
+            let lower_case_text = text.to_lowercase();
+            let naive = bias_sum / n + tokenize(&lower_case_text).iter().map(|token| weight_sums.get(*token).copied().unwrap_or(0.0) / n).sum::<f64>();
+            assert!((averaged.decision_function(text) - naive).abs() < 1e-12, "{text}");
+        }
+    }
+
+    // PA-I з великим C після одного кроку дає рівно зазор 1; мале C обмежує крок
+    #[test]
+    fn passive_aggressive_reaches_unit_margin() {
+        for (text, label) in MESSAGES {
+            let y = if label == "Spam" { 1.0 } else { -1.0 };
+            let mut model = OnlineClassifier::new(OnlineAlgorithm::PassiveAggressive(1e9), "Spam");
+            model.partial_fit(text, label);
+            assert!((y * model.decision_function(text) - 1.0).abs() < 1e-12, "{text}");
+            // повідомлення з зазором 1 вже не змінює ваг
+            let weights = model.weights.clone();
+            model.partial_fit(text, label);
+            assert_eq!(model.weights, weights);
+
+            let mut capped = OnlineClassifier::new(OnlineAlgorithm::PassiveAggressive(0.01), "Spam");
+            capped.partial_fit(text, label);
+            assert!((capped.bias - 0.01 * y).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn winnow_promotes_and_demotes_on_mistakes() {
+        let mut model = OnlineClassifier::new(OnlineAlgorithm::Winnow(2.0), "Spam");
+        // середня вага нових слів 1 - не більше порогу, тож спам пропущено: ваги його слів подвоюються
+        model.partial_fit("win money", "Spam");
+        assert_eq!((model.weight("win"), model.weight("money")), (2.0, 2.0));
+        // середня вага (2 + 1) / 2 > 1 - хибна тривога: ваги слів діляться на 2
+        model.partial_fit("money lunch", "Ham");
+        assert_eq!((model.weight("money"), model.weight("lunch"), model.weight("win")), (1.0, 0.5, 2.0));
+        // правильна відповідь ваг не змінює
+        model.partial_fit("win prize", "Spam");
+        assert_eq!((model.weight("win"), model.weight("prize")), (2.0, 1.0));
+        assert_eq!((model.seen(), model.mistakes()), (3, 2));
+        assert!(model.predict("win win money") && !model.predict("lunch money"));
+    }
+}
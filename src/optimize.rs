@@ -0,0 +1,140 @@
+// Чисельна оптимізація для лінійних моделей та нейромереж: f повертає значення функції та її градієнт
+use crate::util::dot;
+
+// Норма L∞: найбільший модуль компоненти
+fn max_abs(values: &[f64]) -> f64 {
+    values.iter().fold(0.0, |max: f64, v| max.max(v.abs()))
+}
+
+// Пошук кроку з поверненням (умова Армійо) вздовж напрямку спуску direction
+fn backtracking(f: &impl Fn(&[f64]) -> (f64, Vec<f64>), x: &[f64], value: f64, gradient: &[f64], direction: &[f64], initial_step: f64) -> Option<(Vec<f64>, f64, Vec<f64>)> {
+    let slope = dot(gradient, direction);
+    if slope >= 0.0 {
+        return None;
+    }
+    let mut step = initial_step;
+    for _ in 0..50 {
+        let candidate: Vec<f64> = x.iter().zip(direction).map(|(xi, di)| xi + step * di).collect();
+        let (candidate_value, candidate_gradient) = f(&candidate);
+        if candidate_value.is_finite() && candidate_value <= value + 1e-4 * step * slope {
+            return Some((candidate, candidate_value, candidate_gradient));
+        }
+        step *= 0.5;
+    }
+    None
+}
+
+// L-BFGS (Nocedal, 1980) з пам'яттю з memory останніх пар (s, y); повертає точку та кількість ітерацій
+pub fn lbfgs(f: impl Fn(&[f64]) -> (f64, Vec<f64>), x0: Vec<f64>, memory: usize, max_iter: usize, tolerance: f64) -> (Vec<f64>, usize) {
+    let mut x = x0;
+    let (mut value, mut gradient) = f(&x);
+    let mut history: Vec<(Vec<f64>, Vec<f64>, f64)> = Vec::new();  // (s, y, 1 / (y·s))
+    for iteration in 0..max_iter {
+        if max_abs(&gradient) < tolerance {
+            return (x, iteration);
+        }
+        // Двоциклова рекурсія: напрямок -H * gradient
+        let mut q = gradient.clone();
+        let mut alphas = Vec::with_capacity(history.len());
+        for (s, y, rho) in history.iter().rev() {
+            let alpha = rho * dot(s, &q);
+            q.iter_mut().zip(y).for_each(|(qi, yi)| *qi -= alpha * yi);
+            alphas.push(alpha);
+        }
+        let gamma = history.last().map(|(s, y, _)| dot(s, y) / dot(y, y)).unwrap_or(1.0 / max_abs(&gradient).max(1.0));
+        q.iter_mut().for_each(|qi| *qi *= gamma);
+        for ((s, y, rho), alpha) in history.iter().zip(alphas.iter().rev()) {
+            let beta = rho * dot(y, &q);
+            q.iter_mut().zip(s).for_each(|(qi, si)| *qi += (alpha - beta) * si);
+        }
+        let direction: Vec<f64> = q.iter().map(|qi| -qi).collect();
+
+        let step = backtracking(&f, &x, value, &gradient, &direction, 1.0)
+            // напрямок L-BFGS не є напрямком спуску - починаємо пам'ять заново з антиградієнта
+            .or_else(|| {
+                history.clear();
+                let steepest: Vec<f64> = gradient.iter().map(|g| -g).collect();
+                backtracking(&f, &x, value, &gradient, &steepest, 1.0 / max_abs(&gradient).max(1.0))
+            });
+        let Some((next, next_value, next_gradient)) = step else { return (x, iteration + 1) };
+
+        let s: Vec<f64> = next.iter().zip(&x).map(|(a, b)| a - b).collect();
+        let y: Vec<f64> = next_gradient.iter().zip(&gradient).map(|(a, b)| a - b).collect();
+        let ys = dot(&y, &s);
+        if ys > 1e-10 {
+            history.push((s, y, 1.0 / ys));
+            if history.len() > memory {
+                history.remove(0);
+            }
+        }
+        let converged = (value - next_value).abs() <= tolerance * value.abs().max(1.0);
+        x = next;
+        value = next_value;
+        gradient = next_gradient;
+        if converged {
+            return (x, iteration + 1);
+        }
+    }
+    (x, max_iter)
+}
+
+// Проксимальний градієнтний спуск для f(x) + g(x), де f гладка, а g задана проксимальним оператором
+// prox(x, step) = argmin_z g(z) + |z - x|^2 / (2 step); без g (prox - тотожність) це звичайний градієнтний спуск.
+// Крок починається з learning_rate і зменшується вдвічі, доки не виконано умову достатнього спадання.
+pub fn proximal_gradient_descent(f: impl Fn(&[f64]) -> (f64, Vec<f64>), prox: impl Fn(&mut [f64], f64), x0: Vec<f64>, learning_rate: f64, max_iter: usize, tolerance: f64) -> (Vec<f64>, usize) {
+    let mut x = x0;
+    let (mut value, mut gradient) = f(&x);
+    let mut step = learning_rate;
+    for iteration in 0..max_iter {
+        let (next, next_value, next_gradient) = loop {
+            let mut candidate: Vec<f64> = x.iter().zip(&gradient).map(|(xi, gi)| xi - step * gi).collect();
+            prox(&mut candidate, step);
+            let (candidate_value, candidate_gradient) = f(&candidate);
+            let difference: Vec<f64> = candidate.iter().zip(&x).map(|(a, b)| a - b).collect();
+            let bound = value + dot(&gradient, &difference) + dot(&difference, &difference) / (2.0 * step);
+            if (candidate_value.is_finite() && candidate_value <= bound + 1e-12 * value.abs()) || step < 1e-12 {
+                break (candidate, candidate_value, candidate_gradient);
+            }
+            step *= 0.5;
+        };
+        let change = max_abs(&next.iter().zip(&x).map(|(a, b)| a - b).collect::<Vec<f64>>());
+        x = next;
+        value = next_value;
+        gradient = next_gradient;
+        if change < tolerance * step {
+            return (x, iteration + 1);
+        }
+    }
+    (x, max_iter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Функція Розенброка: мінімум 0 у точці (1, 1)
+    fn rosenbrock(x: &[f64]) -> (f64, Vec<f64>) {
+        let (a, b) = (x[0], x[1]);
+        let value = (1.0 - a).powi(2) + 100.0 * (b - a * a).powi(2);
+        let gradient = vec![-2.0 * (1.0 - a) - 400.0 * a * (b - a * a), 200.0 * (b - a * a)];
+        (value, gradient)
+    }
+
+    #[test]
+    fn lbfgs_finds_rosenbrock_minimum() {
+        let (x, n_iter) = lbfgs(rosenbrock, vec![-1.2, 1.0], 10, 500, 1e-10);
+        assert!(n_iter < 500);
+        assert!((x[0] - 1.0).abs() < 1e-5 && (x[1] - 1.0).abs() < 1e-5, "{x:?}");
+    }
+
+    // 0.5 (x - c)^2 + |x|: розв'язок - м'яке порогування c на 1
+    #[test]
+    fn proximal_gradient_descent_soft_thresholds() {
+        let soft_threshold = |x: &mut [f64], step: f64| x.iter_mut().for_each(|v| *v = v.signum() * (v.abs() - step).max(0.0));
+        for (c, expected) in [(3.0, 2.0), (0.5, 0.0), (-2.5, -1.5)] {
+            let f = |x: &[f64]| (0.5 * (x[0] - c) * (x[0] - c), vec![x[0] - c]);
+            let (x, _) = proximal_gradient_descent(f, soft_threshold, vec![0.0], 1.0, 100, 1e-12);
+            assert!((x[0] - expected).abs() < 1e-9, "c = {c}: {x:?}");
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::error::Error;
+use std::fs;
+use crate::preprocessing::{parse_values_line, quantile, sorted_column, values_line, Transformer};
+
+// Обрізання значень кожної ознаки до [Q_low - m * IQR, Q_high + m * IQR], де IQR = Q_high - Q_low
+// (за замовчуванням Q_low, Q_high - квартилі, m = 1.5; при m = 0 - обрізання за перцентилями)
+pub struct OutlierCapper {
+    multiplier: f64,
+    low_quantile: f64,
+    high_quantile: f64,
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+}
+
+impl OutlierCapper {
+    pub fn new(multiplier: f64) -> Self {
+        OutlierCapper { multiplier, low_quantile: 0.25, high_quantile: 0.75, lower: Vec::new(), upper: Vec::new() }
+    }
+
+    // Перцентилі у відсотках, наприклад (5.0, 95.0)
+    pub fn with_percentiles(mut self, low: f64, high: f64) -> Self {
+        self.low_quantile = low / 100.0;
+        self.high_quantile = high / 100.0;
+        self
+    }
+
+    pub fn lower_bounds(&self) -> &[f64] {
+        &self.lower
+    }
+
+    pub fn upper_bounds(&self) -> &[f64] {
+        &self.upper
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let mut capper = match header.as_slice() {
+            ["outlier_capper", multiplier, low, high] => OutlierCapper {
+                low_quantile: low.parse()?,
+                high_quantile: high.parse()?,
+                ..OutlierCapper::new(multiplier.parse()?)
+            },
+            _ => return Err(format!("невідомий заголовок обрізання викидів: {:?}", header).into()),
+        };
+        capper.lower = parse_values_line(lines.next(), "lower")?;
+        capper.upper = parse_values_line(lines.next(), "upper")?;
+        Ok(capper)
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        OutlierCapper::from_text(&fs::read_to_string(path)?)
+    }
+}
+
+// Межі [Q_low - m * IQR, Q_high + m * IQR] для кожного стовпця
+fn iqr_bounds(x: &[Vec<f64>], multiplier: f64, low_quantile: f64, high_quantile: f64) -> (Vec<f64>, Vec<f64>) {
+    let dims = x.first().map(|row| row.len()).unwrap_or(0);
+    (0..dims)
+        .map(|d| {
+            let values = sorted_column(x, d);
+            let low = quantile(&values, low_quantile);
+            let high = quantile(&values, high_quantile);
+            (low - multiplier * (high - low), high + multiplier * (high - low))
+        })
+        .unzip()
+}
+
+impl Transformer for OutlierCapper {
+    fn fit(&mut self, x: &[Vec<f64>]) {
+        (self.lower, self.upper) = iqr_bounds(x, self.multiplier, self.low_quantile, self.high_quantile);
+    }
+
+    // NaN залишаються без змін (пропуски - справа імпутера)
+    fn transform(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        x.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(self.lower.iter().zip(self.upper.iter()))
+                    .map(|(&value, (&lower, &upper))| if value < lower { lower } else if value > upper { upper } else { value })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Квантилі зберігаються частками (без множення на 100, щоб не втрачати точність)
+    fn to_text(&self) -> String {
+        format!("outlier_capper {} {} {}\n{}\n{}\n",
+                self.multiplier, self.low_quantile, self.high_quantile,
+                values_line("lower", &self.lower), values_line("upper", &self.upper))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OutlierRule {
+    // |x - середнє| > поріг * стандартне відхилення
+    ZScore(f64),
+    // за межами [Q1 - m * IQR, Q3 + m * IQR]
+    Iqr(f64),
+}
+
+// Які значення датасету вважаються викидами
+pub struct OutlierReport {
+    pub flags: Vec<Vec<bool>>,  // flags[рядок][ознака]
+    pub per_column: Vec<usize>,  // кількість викидів у кожному стовпці
+    pub rows: Vec<usize>,  // рядки, що містять хоча б один викид
+}
+
+pub fn detect_outliers(x: &[Vec<f64>], rule: OutlierRule) -> OutlierReport {
+    let dims = x.first().map(|row| row.len()).unwrap_or(0);
+    let (lower, upper) = match rule {
+        OutlierRule::Iqr(multiplier) => iqr_bounds(x, multiplier, 0.25, 0.75),
+        OutlierRule::ZScore(threshold) => (0..dims)
+            .map(|d| {
+                let values = sorted_column(x, d);
+                let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
+                let std = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len().max(1) as f64).sqrt();
+                (mean - threshold * std, mean + threshold * std)
+            })
+            .unzip(),
+    };
+    let flags: Vec<Vec<bool>> = x.iter()
+        .map(|row| {
+            row.iter()
+                .zip(lower.iter().zip(upper.iter()))
+                .map(|(&value, (&lower, &upper))| value < lower || value > upper)
+                .collect()
+        })
+        .collect();
+    let per_column = (0..dims).map(|d| flags.iter().filter(|row| row[d]).count()).collect();
+    let rows = (0..flags.len()).filter(|&i| flags[i].iter().any(|&f| f)).collect();
+    OutlierReport { flags, per_column, rows }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capper_round_trip_keeps_quantiles() {
+        let x: Vec<Vec<f64>> = (0..50).map(|i| vec![i as f64, (i * i) as f64 / 7.0]).collect();
+        let mut capper = OutlierCapper::new(0.0).with_percentiles(7.0, 93.0);
+        capper.fit(&x);
+        let text = capper.to_text();
+        let loaded = OutlierCapper::from_text(&text).unwrap();
+        assert_eq!(loaded.to_text(), text);
+        assert_eq!((loaded.low_quantile, loaded.high_quantile), (capper.low_quantile, capper.high_quantile));
+        assert_eq!(loaded.transform(&x), capper.transform(&x));
+    }
+}
@@ -0,0 +1,233 @@
+use std::error::Error;
+use std::fs;
+use crate::distance::covariance_matrix;
+use crate::preprocessing::{parse_values_line, values_line, Transformer};
+
+// Скільки головних компонент залишити
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Components {
+    All,
+    Count(usize),
+    // найменша кількість компонент, що пояснює не менше цієї частки дисперсії
+    VarianceRatio(f64),
+}
+
+// Метод головних компонент через власне розкладання коваріаційної матриці
+pub struct Pca {
+    components: Components,
+    whiten: bool,
+    mean: Vec<f64>,
+    axes: Vec<Vec<f64>>,  // головні осі (власні вектори), від найбільшої дисперсії
+    explained_variance: Vec<f64>,
+    explained_variance_ratio: Vec<f64>,
+}
+
+// Власні значення та вектори симетричної матриці (метод обертань Якобі); вектори - стовпці результату
+fn symmetric_eigen(matrix: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = matrix.len();
+    let mut a = matrix.to_vec();
+    let mut v: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+    let norm: f64 = a.iter().flatten().map(|v| v * v).sum();
+    for _ in 0..100 {
+        let off_diagonal: f64 = (0..n).flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off_diagonal <= 1e-24 * norm {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                // кут, що обнуляє a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (upper, lower) = a.split_at_mut(q);
+                for (apk, aqk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
+
+impl Pca {
+    pub fn new(components: Components) -> Self {
+        Pca {
+            components,
+            whiten: false,
+            mean: Vec::new(),
+            axes: Vec::new(),
+            explained_variance: Vec::new(),
+            explained_variance_ratio: Vec::new(),
+        }
+    }
+
+    // Після проєкції кожна компонента має одиничну дисперсію
+    pub fn with_whiten(mut self, whiten: bool) -> Self {
+        self.whiten = whiten;
+        self
+    }
+
+    pub fn n_components(&self) -> usize {
+        self.axes.len()
+    }
+
+    pub fn components(&self) -> &[Vec<f64>] {
+        &self.axes
+    }
+
+    // Дисперсія вздовж кожної залишеної компоненти
+    pub fn explained_variance(&self) -> &[f64] {
+        &self.explained_variance
+    }
+
+    // Частка загальної дисперсії, яку пояснює кожна залишена компонента
+    pub fn explained_variance_ratio(&self) -> &[f64] {
+        &self.explained_variance_ratio
+    }
+
+    // Відновлення вихідних ознак з головних компонент (з втратою відкинутих компонент)
+    pub fn inverse_transform(&self, z: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        z.iter()
+            .map(|row| {
+                let mut restored = self.mean.clone();
+                for ((value, axis), variance) in row.iter().zip(self.axes.iter()).zip(self.explained_variance.iter()) {
+                    let value = if self.whiten { value * variance.sqrt() } else { *value };
+                    restored.iter_mut().zip(axis.iter()).for_each(|(r, a)| *r += value * a);
+                }
+                restored
+            })
+            .collect()
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let whiten = match header.as_slice() {
+            ["pca", whiten] => whiten.parse()?,
+            _ => return Err(format!("невідомий заголовок PCA: {:?}", header).into()),
+        };
+        let mean = parse_values_line(lines.next(), "mean")?;
+        let explained_variance = parse_values_line(lines.next(), "variance")?;
+        let explained_variance_ratio = parse_values_line(lines.next(), "ratio")?;
+        let axes: Vec<Vec<f64>> = lines.map(|line| parse_values_line(Some(line), "axis")).collect::<Result<_, _>>()?;
+        Ok(Pca { components: Components::Count(axes.len()), whiten, mean, axes, explained_variance, explained_variance_ratio })
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        Pca::from_text(&fs::read_to_string(path)?)
+    }
+}
+
+impl Transformer for Pca {
+    fn fit(&mut self, x: &[Vec<f64>]) {
+        let n = x.len().max(1) as f64;
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        self.mean = (0..dims).map(|d| x.iter().map(|row| row[d]).sum::<f64>() / n).collect();
+
+        let (eigenvalues, eigenvectors) = symmetric_eigen(&covariance_matrix(x));
+        let mut order: Vec<usize> = (0..dims).collect();
+        // total_cmp: NaN у даних (незаповнені пропуски) не повинні зупиняти програму
+        order.sort_by(|&a, &b| eigenvalues[b].total_cmp(&eigenvalues[a]));
+        let total: f64 = eigenvalues.iter().map(|v| v.max(0.0)).sum();
+
+        let keep = match self.components {
+            Components::All => dims,
+            Components::Count(count) => count.min(dims),
+            Components::VarianceRatio(ratio) => {
+                let mut cumulative = 0.0;
+                order.iter()
+                    .position(|&i| {
+                        cumulative += eigenvalues[i].max(0.0) / total;
+                        cumulative >= ratio - 1e-12
+                    })
+                    .map(|position| position + 1)
+                    .unwrap_or(dims)
+            }
+        };
+        self.axes.clear();
+        self.explained_variance.clear();
+        self.explained_variance_ratio.clear();
+        for &i in order.iter().take(keep) {
+            let mut axis: Vec<f64> = eigenvectors.iter().map(|row| row[i]).collect();
+            // знак осі визначений неоднозначно: найбільша за модулем координата робиться додатною
+            let largest = axis.iter().copied().fold(0.0, |best: f64, a| if a.abs() > best.abs() { a } else { best });
+            if largest < 0.0 {
+                axis.iter_mut().for_each(|a| *a = -*a);
+            }
+            let variance = eigenvalues[i].max(0.0);
+            self.axes.push(axis);
+            self.explained_variance.push(variance);
+            self.explained_variance_ratio.push(if total > 0.0 { variance / total } else { 0.0 });
+        }
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        x.iter()
+            .map(|row| {
+                self.axes.iter()
+                    .zip(self.explained_variance.iter())
+                    .map(|(axis, variance)| {
+                        let projection: f64 = row.iter().zip(self.mean.iter()).zip(axis.iter()).map(|((v, m), a)| (v - m) * a).sum();
+                        if self.whiten && *variance > 0.0 { projection / variance.sqrt() } else { projection }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("pca {}\n{}\n{}\n{}\n", self.whiten,
+                               values_line("mean", &self.mean),
+                               values_line("variance", &self.explained_variance),
+                               values_line("ratio", &self.explained_variance_ratio));
+        for axis in &self.axes {
+            text.push_str(&values_line("axis", axis));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::load_numeric_dataset;
+
+    #[test]
+    fn explained_variance_ratios_sum_to_one() {
+        let (x, _, _) = load_numeric_dataset("datasets/iris.csv", 1).unwrap();
+        let mut pca = Pca::new(Components::All);
+        pca.fit(&x);
+        let ratios = pca.explained_variance_ratio();
+        assert_eq!(ratios.len(), 4);
+        assert!((ratios.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(ratios.windows(2).all(|pair| pair[0] >= pair[1]));
+        // усі компоненти - поворот, тож обернене перетворення відновлює дані
+        let restored = pca.inverse_transform(&pca.transform(&x));
+        assert!(restored.iter().flatten().zip(x.iter().flatten()).all(|(a, b)| (a - b).abs() < 1e-9));
+
+        let mut reduced = Pca::new(Components::VarianceRatio(0.95));
+        reduced.fit(&x);
+        let kept: f64 = ratios[..reduced.n_components()].iter().sum();
+        assert!(kept >= 0.95 && kept - ratios[reduced.n_components() - 1] < 0.95);
+    }
+}
+
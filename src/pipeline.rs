@@ -0,0 +1,219 @@
+use std::error::Error;
+use std::fs;
+use crate::dummy::{baseline_accuracy, lift, print_lift, DummyClassifier, ZeroRClassifier};
+use crate::gradient_boosting::GradientBoostingClassifier;
+use crate::knn::{KNNClassifier, RadiusNeighborsClassifier};
+use crate::logistic::LogisticRegression;
+use crate::mlp::MlpClassifier;
+use crate::naive_bayes::GaussianNaiveBayes;
+use crate::svm::SvmClassifier;
+use crate::preprocessing::{load_transformer, Transformer};
+use crate::util::{accuracy, select_rows, stratified_k_fold};
+
+// Класифікатор числових ознак, який можна поставити в кінець Pipeline
+pub trait Classifier {
+    fn fit(&mut self, x: &[Vec<f64>], y: &[String]);
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String>;
+    // Текстове представлення навченої моделі (перший рядок - тип моделі)
+    fn to_text(&self) -> String;
+}
+
+// Відновлення збереженого класифікатора за його заголовком.
+// Моделі, що навчаються на рядкових категоріях (ID3, OneR, категоріальний наївний Баєс, PRISM, RIPPER), не реалізують
+// Classifier і сюди не потрапляють: Pipeline працює лише з числовою матрицею після перетворень
+pub fn load_classifier(text: &str) -> Result<Box<dyn Classifier>, Box<dyn Error>> {
+    let kind = text.split_whitespace().next().unwrap_or_default();
+    Ok(match kind {
+        "knn" => Box::new(KNNClassifier::from_text(text)?),
+        "radius_neighbors" => Box::new(RadiusNeighborsClassifier::from_text(text)?),
+        "gaussian_nb" => Box::new(GaussianNaiveBayes::from_text(text)?),
+        "zero_r" => Box::new(ZeroRClassifier::from_text(text)?),
+        "dummy" => Box::new(DummyClassifier::from_text(text)?),
+        "logistic_regression" => Box::new(LogisticRegression::from_text(text)?),
+        "svm" => Box::new(SvmClassifier::from_text(text)?),
+        "mlp" => Box::new(MlpClassifier::from_text(text)?),
+        "gradient_boosting" => Box::new(GradientBoostingClassifier::from_text(text)?),
+        _ => return Err(format!("невідомий класифікатор: {kind}").into()),
+    })
+}
+
+// Ланцюжок перетворень і класифікатор (як sklearn Pipeline):
+// fit навчає кожен крок на виході попереднього, predict застосовує ті самі навчені кроки
+pub struct Pipeline {
+    steps: Vec<(String, Box<dyn Transformer>)>,
+    classifier: Box<dyn Classifier>,
+}
+
+// Рядок, яким закінчується кожен компонент у збереженому Pipeline
+const END: &str = "end";
+
+impl Pipeline {
+    pub fn new(classifier: impl Classifier + 'static) -> Self {
+        Pipeline { steps: Vec::new(), classifier: Box::new(classifier) }
+    }
+
+    pub fn with_step(mut self, name: &str, transformer: impl Transformer + 'static) -> Self {
+        self.steps.push((name.to_string(), Box::new(transformer)));
+        self
+    }
+
+    pub fn step_names(&self) -> Vec<&str> {
+        self.steps.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        let mut data = x.to_vec();
+        for (_, transformer) in self.steps.iter_mut() {
+            data = transformer.fit_transform(&data);
+        }
+        self.classifier.fit(&data, y);
+    }
+
+    // Дані після всіх кроків перетворення (те, що бачить класифікатор)
+    pub fn transform(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let mut data = x.to_vec();
+        for (_, transformer) in &self.steps {
+            data = transformer.transform(&data);
+        }
+        data
+    }
+
+    pub fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        self.classifier.predict(&self.transform(x))
+    }
+
+    pub fn score(&self, x: &[Vec<f64>], y: &[String]) -> f64 {
+        accuracy(y, &self.predict(x))
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("pipeline\n");
+        for (name, transformer) in &self.steps {
+            text.push_str(&format!("step {name}\n{}{END}\n", transformer.to_text()));
+        }
+        text.push_str(&format!("classifier\n{}{END}\n", self.classifier.to_text()));
+        text
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        if lines.next() != Some("pipeline") {
+            return Err("очікувався заголовок pipeline".into());
+        }
+        let mut steps = Vec::new();
+        let mut classifier = None;
+        while let Some(line) = lines.next() {
+            let mut body = String::new();
+            for body_line in lines.by_ref().take_while(|&l| l != END) {
+                body.push_str(body_line);
+                body.push('\n');
+            }
+            if let Some(name) = line.strip_prefix("step ") {
+                steps.push((name.to_string(), load_transformer(&body)?));
+            } else if line == "classifier" {
+                classifier = Some(load_classifier(&body)?);
+            } else {
+                return Err(format!("невідомий розділ pipeline: {line}").into());
+            }
+        }
+        Ok(Pipeline { steps, classifier: classifier.ok_or("у pipeline немає класифікатора")? })
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        Pipeline::from_text(&fs::read_to_string(path)?)
+    }
+}
+
+// Результат перехресної перевірки: точність моделі й ZeroR на кожній частині
+pub struct CrossValidation {
+    pub scores: Vec<f64>,
+    pub baselines: Vec<f64>,
+}
+
+impl CrossValidation {
+    pub fn mean(&self) -> f64 {
+        self.scores.iter().sum::<f64>() / self.scores.len().max(1) as f64
+    }
+
+    pub fn baseline(&self) -> f64 {
+        self.baselines.iter().sum::<f64>() / self.baselines.len().max(1) as f64
+    }
+
+    // Lift середньої точності над середньою точністю ZeroR
+    pub fn lift(&self) -> f64 {
+        lift(self.mean(), self.baseline())
+    }
+
+    pub fn print(&self, name: &str) {
+        print_lift(name, self.mean(), self.baseline());
+    }
+}
+
+// Стратифікована k-кратна перехресна перевірка: для кожної частини будується новий Pipeline,
+// і всі його кроки навчаються лише на навчальних частинах (без витоку статистик тестової частини);
+// ZeroR навчається на тих самих частинах як базовий рівень
+pub fn cross_validate(build: impl Fn() -> Pipeline, x: &[Vec<f64>], y: &[String], folds: usize, seed: u64) -> CrossValidation {
+    let (scores, baselines) = stratified_k_fold(y, folds, seed)
+        .into_iter()
+        .map(|(train, test)| {
+            let (y_train, y_test) = (select_rows(y, &train), select_rows(y, &test));
+            let mut pipeline = build();
+            pipeline.fit(&select_rows(x, &train), &y_train);
+            (pipeline.score(&select_rows(x, &test), &y_test), baseline_accuracy(&y_train, &y_test))
+        })
+        .unzip();
+    CrossValidation { scores, baselines }
+}
+
+// Лише точності моделі на частинах
+pub fn cross_val_score(build: impl Fn() -> Pipeline, x: &[Vec<f64>], y: &[String], folds: usize, seed: u64) -> Vec<f64> {
+    cross_validate(build, x, y, folds, seed).scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::imputer::{SimpleImputer, Strategy};
+    use crate::outliers::OutlierCapper;
+    use crate::preprocessing::{Scaler, Scaling};
+    use crate::util::{load_numeric_dataset_with_missing, train_test_split_indices};
+
+    #[test]
+    fn pipeline_round_trip() {
+        let (x, y, _) = load_numeric_dataset_with_missing("datasets/vehicle.csv", 0).unwrap();
+        let (train, test) = train_test_split_indices(&y, 0.3, 3);
+        let x_test = select_rows(&x, &test);
+        let classifiers: Vec<Box<dyn Fn() -> Pipeline>> = vec![
+            Box::new(|| Pipeline::new(KNNClassifier::new(5))),
+            Box::new(|| Pipeline::new(RadiusNeighborsClassifier::new(3.0, "outlier"))),
+        ];
+        for build in classifiers {
+            let mut model = build()
+                .with_step("impute", SimpleImputer::new(Strategy::Median))
+                .with_step("outlier", OutlierCapper::new(1.5))
+                .with_step("scale", Scaler::new(Scaling::Standard));
+            model.fit(&select_rows(&x, &train), &select_rows(&y, &train));
+            let restored = Pipeline::from_text(&model.to_text()).unwrap();
+            assert_eq!(restored.step_names(), model.step_names());
+            assert_eq!(restored.to_text(), model.to_text());
+            assert_eq!(restored.predict(&x_test), model.predict(&x_test));
+        }
+    }
+
+    #[test]
+    fn zero_r_pipeline_has_unit_lift() {
+        let (x, y, _) = load_numeric_dataset_with_missing("datasets/vehicle.csv", 0).unwrap();
+        let zero_r = cross_validate(|| Pipeline::new(ZeroRClassifier::new()), &x, &y, 4, 1);
+        assert_eq!(zero_r.scores, zero_r.baselines);
+        assert_eq!(zero_r.lift(), 1.0);
+
+        let knn = cross_validate(|| Pipeline::new(KNNClassifier::new(5)).with_step("impute", SimpleImputer::new(Strategy::Median)), &x, &y, 4, 1);
+        assert_eq!(knn.baselines, zero_r.baselines);
+        assert!(knn.lift() > 1.0);
+    }
+}
@@ -0,0 +1,236 @@
+use std::error::Error;
+use std::fs;
+use crate::imputer::{KNNImputer, SimpleImputer};
+use crate::outliers::OutlierCapper;
+use crate::pca::Pca;
+
+// Перетворення числових ознак: параметри запам'ятовуються у fit і застосовуються у transform
+// (до тестових даних - ті самі параметри, що були знайдені на навчальних)
+pub trait Transformer {
+    fn fit(&mut self, x: &[Vec<f64>]);
+    fn transform(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>>;
+
+    fn fit_transform(&mut self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        self.fit(x);
+        self.transform(x)
+    }
+
+    // Текстове представлення навчених параметрів (перший рядок - тип перетворення)
+    fn to_text(&self) -> String;
+
+    fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+}
+
+// Відновлення збереженого перетворення за його заголовком
+pub fn load_transformer(text: &str) -> Result<Box<dyn Transformer>, Box<dyn Error>> {
+    let kind = text.split_whitespace().next().unwrap_or_default();
+    Ok(match kind {
+        "scaler" => Box::new(Scaler::from_text(text)?),
+        "imputer" => Box::new(SimpleImputer::from_text(text)?),
+        "knn_imputer" => Box::new(KNNImputer::from_text(text)?),
+        "outlier_capper" => Box::new(OutlierCapper::from_text(text)?),
+        "pca" => Box::new(Pca::from_text(text)?),
+        _ => return Err(format!("невідоме перетворення: {kind}").into()),
+    })
+}
+
+// Значення стовпця d без пропусків (NaN)
+pub fn column(x: &[Vec<f64>], d: usize) -> Vec<f64> {
+    x.iter().map(|row| row[d]).filter(|v| !v.is_nan()).collect()
+}
+
+// Квантиль q відсортованих значень з лінійною інтерполяцією (як numpy.percentile за замовчуванням)
+pub fn quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let position = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+pub fn sorted_column(x: &[Vec<f64>], d: usize) -> Vec<f64> {
+    let mut values = column(x, d);
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    values
+}
+
+// Рядок "назва v1 v2 ..." для збереження параметрів
+pub fn values_line(name: &str, values: &[f64]) -> String {
+    let mut line = name.to_string();
+    for value in values {
+        line.push(' ');
+        line.push_str(&value.to_string());
+    }
+    line
+}
+
+// Розбір рядка, записаного values_line
+pub fn parse_values_line(line: Option<&str>, name: &str) -> Result<Vec<f64>, Box<dyn Error>> {
+    let line = line.ok_or(format!("немає рядка {name}"))?;
+    let mut tokens = line.split_whitespace();
+    if tokens.next() != Some(name) {
+        return Err(format!("очікувався рядок {name}, отримано: {line}").into());
+    }
+    Ok(tokens.map(|token| token.parse::<f64>()).collect::<Result<Vec<f64>, _>>()?)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Scaling {
+    // (x - середнє) / стандартне відхилення
+    Standard,
+    // лінійно у відрізок [min, max]
+    MinMax(f64, f64),
+    // (x - медіана) / IQR - стійке до викидів
+    Robust,
+    // x / max|x| - зберігає нулі та знаки (для розріджених даних)
+    MaxAbs,
+}
+
+// Масштабування кожної ознаки: x' = (x - offset) / scale
+pub struct Scaler {
+    scaling: Scaling,
+    offsets: Vec<f64>,
+    scales: Vec<f64>,
+}
+
+impl Scaler {
+    pub fn new(scaling: Scaling) -> Self {
+        Scaler { scaling, offsets: Vec::new(), scales: Vec::new() }
+    }
+
+    pub fn scaling(&self) -> Scaling {
+        self.scaling
+    }
+
+    pub fn offsets(&self) -> &[f64] {
+        &self.offsets
+    }
+
+    pub fn scales(&self) -> &[f64] {
+        &self.scales
+    }
+
+    pub fn inverse_transform(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        x.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(self.offsets.iter().zip(self.scales.iter()))
+                    .map(|(value, (offset, scale))| value * scale + offset)
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let scaling = match header.as_slice() {
+            ["scaler", "standard"] => Scaling::Standard,
+            ["scaler", "robust"] => Scaling::Robust,
+            ["scaler", "max_abs"] => Scaling::MaxAbs,
+            ["scaler", "min_max", min, max] => Scaling::MinMax(min.parse()?, max.parse()?),
+            _ => return Err(format!("невідомий заголовок масштабування: {:?}", header).into()),
+        };
+        let offsets = parse_values_line(lines.next(), "offsets")?;
+        let scales = parse_values_line(lines.next(), "scales")?;
+        if offsets.len() != scales.len() {
+            return Err("різна кількість offsets та scales".into());
+        }
+        Ok(Scaler { scaling, offsets, scales })
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        Scaler::from_text(&fs::read_to_string(path)?)
+    }
+}
+
+impl Transformer for Scaler {
+    // Пропуски (NaN) ігноруються при обчисленні параметрів і залишаються NaN після перетворення
+    fn fit(&mut self, x: &[Vec<f64>]) {
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        self.offsets.clear();
+        self.scales.clear();
+        for d in 0..dims {
+            let values = sorted_column(x, d);
+            let (offset, scale) = match self.scaling {
+                Scaling::Standard => {
+                    let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
+                    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len().max(1) as f64;
+                    (mean, variance.sqrt())
+                }
+                Scaling::MinMax(low, high) => {
+                    let min = values.first().copied().unwrap_or(0.0);
+                    let max = values.last().copied().unwrap_or(0.0);
+                    let scale = (max - min) / (high - low);
+                    (min - low * scale, scale)
+                }
+                Scaling::Robust => (quantile(&values, 0.5), quantile(&values, 0.75) - quantile(&values, 0.25)),
+                Scaling::MaxAbs => (0.0, values.iter().fold(0.0, |max: f64, v| max.max(v.abs()))),
+            };
+            // Сталий стовпець не масштабується (ділення на нуль)
+            let scale = if scale.is_finite() && scale > 0.0 { scale } else { 1.0 };
+            self.offsets.push(if offset.is_finite() { offset } else { 0.0 });
+            self.scales.push(scale);
+        }
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        x.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(self.offsets.iter().zip(self.scales.iter()))
+                    .map(|(value, (offset, scale))| (value - offset) / scale)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn to_text(&self) -> String {
+        let header = match self.scaling {
+            Scaling::Standard => "scaler standard".to_string(),
+            Scaling::Robust => "scaler robust".to_string(),
+            Scaling::MaxAbs => "scaler max_abs".to_string(),
+            Scaling::MinMax(low, high) => format!("scaler min_max {low} {high}"),
+        };
+        format!("{}\n{}\n{}\n", header, values_line("offsets", &self.offsets), values_line("scales", &self.scales))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalers_invert_and_round_trip() {
+        // друга ознака стала, третя має пропуск
+        let x = vec![vec![1.0, 7.0, -2.0], vec![2.0, 7.0, f64::NAN], vec![3.0, 7.0, 4.0], vec![10.0, 7.0, 1.0]];
+        for scaling in [Scaling::Standard, Scaling::MinMax(-1.0, 1.0), Scaling::Robust, Scaling::MaxAbs] {
+            let mut scaler = Scaler::new(scaling);
+            let scaled = scaler.fit_transform(&x);
+            assert!(scaled.iter().all(|row| row[1].is_finite()), "{scaling:?}");
+            assert!(scaled[1][2].is_nan());
+            for (row, restored) in x.iter().zip(scaler.inverse_transform(&scaled)) {
+                for (value, restored) in row.iter().zip(restored) {
+                    assert!(value.is_nan() && restored.is_nan() || (value - restored).abs() < 1e-12, "{scaling:?}");
+                }
+            }
+            let loaded = Scaler::from_text(&scaler.to_text()).unwrap();
+            assert_eq!(loaded.scaling(), scaling);
+            assert_eq!(loaded.to_text(), scaler.to_text());
+        }
+
+        let mut min_max = Scaler::new(Scaling::MinMax(0.0, 1.0));
+        let scaled = min_max.fit_transform(&x);
+        assert_eq!(scaled.iter().map(|row| row[0]).collect::<Vec<f64>>(), vec![0.0, 1.0 / 9.0, 2.0 / 9.0, 1.0]);
+        let mut standard = Scaler::new(Scaling::Standard);
+        let scaled = standard.fit_transform(&x);
+        let mean = scaled.iter().map(|row| row[0]).sum::<f64>() / 4.0;
+        let variance = scaled.iter().map(|row| (row[0] - mean).powi(2)).sum::<f64>() / 4.0;
+        assert!(mean.abs() < 1e-12 && (variance - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::decision_list::{candidate_conditions, predict_decision_list, print_decision_list, update_coverage, DecisionRule};
+use crate::dummy::ZeroRClassifier;
+
+// PRISM (Cendrowska, 1987): правила для кожного класу будуються окремо методом покриття.
+// До правила додається умова з найбільшою точністю p/t (при рівності - з більшим p), доки правило
+// не покриває лише приклади свого класу; покриті приклади вилучаються, і так, доки не покрито весь клас.
+pub struct PrismClassifier {
+    rules: Vec<DecisionRule>,
+    default_class: String,
+}
+
+impl Default for PrismClassifier {
+    fn default() -> Self {
+        PrismClassifier::new()
+    }
+}
+
+impl PrismClassifier {
+    pub fn new() -> Self {
+        PrismClassifier { rules: Vec::new(), default_class: String::new() }
+    }
+
+    pub fn fit(&mut self, x: &[Vec<String>], y: &[String]) {
+        let mut zero_r = ZeroRClassifier::new();
+        zero_r.fit(y);
+        self.default_class = zero_r.default_class().to_string();
+
+        let mut classes: Vec<&String> = y.iter().collect();
+        classes.sort();
+        classes.dedup();
+
+        self.rules.clear();
+        for class in classes {
+            // для кожного класу - знову вся вибірка
+            let mut remaining: Vec<usize> = (0..x.len()).collect();
+            while remaining.iter().any(|&i| y[i] == *class) {
+                let mut rule = DecisionRule::new(Vec::new(), class.clone());
+                let mut covered = remaining.clone();
+                while rule.conditions.is_empty() || covered.iter().any(|&i| y[i] != *class) {
+                    // (умова, p, t): p - приклади класу серед t покритих
+                    let mut best = None;
+                    for condition in candidate_conditions(x, &covered, &rule.conditions) {
+                        let matching: Vec<usize> = covered.iter().copied().filter(|&i| condition.matches(&x[i])).collect();
+                        let p = matching.iter().filter(|&&i| y[i] == *class).count();
+                        let t = matching.len();
+                        let better = match &best {
+                            None => p > 0,
+                            Some((_, best_p, best_t)) => p * best_t > best_p * t || (p * best_t == best_p * t && p > *best_p),
+                        };
+                        if better {
+                            best = Some((condition, p, t));
+                        }
+                    }
+                    let Some((condition, _, _)) = best else { break };
+                    covered.retain(|&i| condition.matches(&x[i]));
+                    rule.conditions.push(condition);
+                }
+                // жодна умова не відокремлює решту прикладів класу (наприклад, усі значення пропущені)
+                if rule.conditions.is_empty() {
+                    break;
+                }
+                remaining.retain(|&i| !rule.covers(&x[i]));
+                self.rules.push(rule);
+            }
+        }
+        update_coverage(&mut self.rules, x, y);
+    }
+
+    pub fn predict(&self, data: &[Vec<String>]) -> Vec<String> {
+        predict_decision_list(&self.rules, &self.default_class, data)
+    }
+
+    pub fn rules(&self) -> &[DecisionRule] {
+        &self.rules
+    }
+
+    pub fn default_class(&self) -> &str {
+        &self.default_class
+    }
+
+    pub fn print_rules(&self, feature_names: &[&str]) {
+        print_decision_list(&self.rules, &self.default_class, feature_names);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Номінальний weather з Witten & Frank (14 прикладів)
+    fn weather() -> (Vec<Vec<String>>, Vec<String>) {
+        ["sunny hot high false no", "sunny hot high true no", "overcast hot high false yes", "rainy mild high false yes",
+         "rainy cool normal false yes", "rainy cool normal true no", "overcast cool normal true yes", "sunny mild high false no",
+         "sunny cool normal false yes", "rainy mild normal false yes", "sunny mild normal true yes", "overcast mild high true yes",
+         "overcast hot normal false yes", "rainy mild high true no"]
+            .iter()
+            .map(|row| {
+                let mut values: Vec<String> = row.split_whitespace().map(|value| value.to_string()).collect();
+                let class = values.pop().unwrap();
+                (values, class)
+            })
+            .unzip()
+    }
+
+    const NAMES: [&str; 4] = ["outlook", "temperature", "humidity", "windy"];
+
+    // Ті самі правила, що й у Weka Prism (класи тут ідуть за алфавітом, тож правила "no" - першими)
+    #[test]
+    fn weather_rules_match_the_textbook() {
+        let (x, y) = weather();
+        let mut model = PrismClassifier::new();
+        model.fit(&x, &y);
+        let rules: Vec<String> = model.rules().iter()
+            .map(|rule| format!("{} -> {}", rule.conditions.iter().map(|c| c.describe(&NAMES)).collect::<Vec<_>>().join(" AND "), rule.class))
+            .collect();
+        assert_eq!(rules, vec![
+            "outlook = sunny AND humidity = high -> no",
+            "outlook = rainy AND windy = true -> no",
+            "outlook = overcast -> yes",
+            "humidity = normal AND windy = false -> yes",
+            "temperature = mild AND humidity = normal -> yes",
+            "outlook = rainy AND windy = false -> yes",
+        ]);
+        assert_eq!(model.predict(&x), y);
+    }
+}
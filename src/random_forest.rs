@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::thread;
+use crate::decision_tree::{DecisionTreeClassifier, FeatureSampler};
+use crate::util::{parallel_map, select_rows, Rng};
+
+pub enum Voting {
+    // кожне дерево віддає один голос за свій клас
+    Hard,
+    // усереднення розподілів класів у листках
+    Soft,
+}
+
+pub struct RandomForestClassifier {
+    n_estimators: usize,
+    max_features: Option<usize>,  // None - sqrt(кількості ознак)
+    voting: Voting,
+    seed: u64,
+    pub n_jobs: usize,
+    trees: Vec<DecisionTreeClassifier>,
+    oob_error: Option<f64>,
+}
+
+// Клас з найбільшою кількістю голосів (при рівності - лексикографічно менший, щоб результат був детермінованим)
+fn argmax_class(votes: &HashMap<String, f64>) -> Option<String> {
+    votes.iter()
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal).then_with(|| b.0.cmp(a.0)))
+        .map(|(class, _)| class.clone())
+}
+
+impl RandomForestClassifier {
+    pub fn new(n_estimators: usize, max_features: Option<usize>, voting: Voting, seed: u64) -> Self {
+        RandomForestClassifier {
+            n_estimators,
+            max_features,
+            voting,
+            seed,
+            n_jobs: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            trees: Vec::new(),
+            oob_error: None,
+        }
+    }
+
+    pub fn fit(&mut self, x: &[Vec<String>], y: &[String]) {
+        let n = y.len();
+        let num_features = x[0].len();
+        let max_features = self.max_features
+            .unwrap_or(((num_features as f64).sqrt().round() as usize).max(1));
+
+        // Кожне дерево має власний seed, тому результат не залежить від кількості потоків
+        let tree_seeds: Vec<u64> = {
+            let mut rng = Rng::new(self.seed);
+            (0..self.n_estimators).map(|_| rng.next_u64()).collect()
+        };
+
+        // Дерева навчаються паралельно: seeds розподіляються між потоками порівну
+        let results: Vec<(DecisionTreeClassifier, Vec<usize>)> = parallel_map(&tree_seeds, self.n_jobs, |&tree_seed| {
+            let mut rng = Rng::new(tree_seed);
+            let bootstrap: Vec<usize> = (0..n).map(|_| rng.gen_range(n)).collect();
+            let mut in_bag = vec![false; n];
+            for &i in &bootstrap {
+                in_bag[i] = true;
+            }
+            let out_of_bag: Vec<usize> = (0..n).filter(|&i| !in_bag[i]).collect();
+
+            let mut tree = DecisionTreeClassifier::new().with_feature_sampler(FeatureSampler::new(max_features, rng.next_u64()));
+            tree.fit(&select_rows(x, &bootstrap), &select_rows(y, &bootstrap));
+            (tree, out_of_bag)
+        });
+
+        // Out-of-bag оцінка: кожен приклад класифікують лише дерева, які його не бачили
+        let mut oob_votes: Vec<HashMap<String, f64>> = vec![HashMap::new(); n];
+        for (tree, out_of_bag) in &results {
+            let rows = select_rows(x, out_of_bag);
+            for (&i, votes) in out_of_bag.iter().zip(self.tree_votes(tree, &rows)) {
+                for (class, vote) in votes {
+                    *oob_votes[i].entry(class).or_default() += vote;
+                }
+            }
+        }
+        let mut evaluated = 0;
+        let mut errors = 0;
+        for (votes, label) in oob_votes.iter().zip(y.iter()) {
+            if let Some(predicted) = argmax_class(votes) {
+                evaluated += 1;
+                if &predicted != label {
+                    errors += 1;
+                }
+            }
+        }
+        self.oob_error = if evaluated > 0 { Some(errors as f64 / evaluated as f64) } else { None };
+        self.trees = results.into_iter().map(|(tree, _)| tree).collect();
+    }
+
+    fn tree_votes(&self, tree: &DecisionTreeClassifier, data: &[Vec<String>]) -> Vec<HashMap<String, f64>> {
+        match self.voting {
+            Voting::Hard => tree.predict(data)
+                .into_iter()
+                .map(|class| HashMap::from([(class, 1.0)]))
+                .collect(),
+            Voting::Soft => tree.predict_proba(data),
+        }
+    }
+
+    // Частки голосів дерев за кожен клас
+    pub fn predict_proba(&self, data: &[Vec<String>]) -> Vec<HashMap<String, f64>> {
+        let mut totals: Vec<HashMap<String, f64>> = vec![HashMap::new(); data.len()];
+        for tree in &self.trees {
+            for (total, votes) in totals.iter_mut().zip(self.tree_votes(tree, data)) {
+                for (class, vote) in votes {
+                    *total.entry(class).or_default() += vote;
+                }
+            }
+        }
+        for total in totals.iter_mut() {
+            let sum: f64 = total.values().sum();
+            if sum > 0.0 {
+                total.values_mut().for_each(|v| *v /= sum);
+            }
+        }
+        totals
+    }
+
+    pub fn predict(&self, data: &[Vec<String>]) -> Vec<String> {
+        self.predict_proba(data)
+            .iter()
+            .map(|votes| argmax_class(votes).unwrap_or_default())
+            .collect()
+    }
+
+    pub fn oob_error(&self) -> Option<f64> {
+        self.oob_error
+    }
+
+    pub fn trees(&self) -> &[DecisionTreeClassifier] {
+        &self.trees
+    }
+
+    // Середня важливість ознак по всіх деревах
+    pub fn feature_importances(&self) -> Vec<f64> {
+        let mut importances: Vec<f64> = Vec::new();
+        for tree in &self.trees {
+            let tree_importances = tree.feature_importances();
+            importances.resize(tree_importances.len().max(importances.len()), 0.0);
+            for (total, value) in importances.iter_mut().zip(tree_importances) {
+                *total += value / self.trees.len() as f64;
+            }
+        }
+        importances
+    }
+}
@@ -0,0 +1,622 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+// Значення ознаки: числове або категоріальне
+#[derive(Clone)]
+pub enum Value {
+    Numeric(f64),
+    Categorical(String),
+}
+
+#[derive(Clone)]
+pub enum Condition {
+    // x <= threshold -> ліва гілка
+    Threshold(f64),
+    // значення з множини -> ліва гілка
+    Categories(HashSet<String>),
+}
+
+impl Condition {
+    fn goes_left(&self, value: &Value) -> bool {
+        match (self, value) {
+            (Condition::Threshold(t), Value::Numeric(v)) => v <= t,
+            (Condition::Categories(set), Value::Categorical(v)) => set.contains(v),
+            _ => false,
+        }
+    }
+
+    // Те саме для числового значення без обгортання у Value
+    fn goes_left_numeric(&self, value: f64) -> bool {
+        matches!(self, Condition::Threshold(t) if value <= *t)
+    }
+
+    fn describe(&self, feature_name: &str) -> String {
+        match self {
+            Condition::Threshold(t) => format!("{} <= {:.4}", feature_name, t),
+            Condition::Categories(set) => {
+                let mut values: Vec<&String> = set.iter().collect();
+                values.sort();
+                format!("{} in {:?}", feature_name, values)
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum RegressionNode {
+    Leaf {
+        value: f64,
+        n_samples: usize,
+        sse: f64,
+        id: usize,  // номер листка у порядку обходу зліва направо (призначається після fit і prune)
+    },
+    Split {
+        feature_index: usize,
+        condition: Condition,
+        left: Box<RegressionNode>,
+        right: Box<RegressionNode>,
+        value: f64,
+        n_samples: usize,
+        sse: f64,
+    },
+}
+
+impl RegressionNode {
+    fn n_samples(&self) -> usize {
+        match self {
+            RegressionNode::Leaf { n_samples, .. } | RegressionNode::Split { n_samples, .. } => *n_samples,
+        }
+    }
+
+    fn predict(&self, row: &[Value]) -> f64 {
+        let mut node = self;
+        loop {
+            match node {
+                RegressionNode::Leaf { value, .. } => return *value,
+                RegressionNode::Split { feature_index, condition, left, right, .. } => {
+                    node = if condition.goes_left(&row[*feature_index]) { left } else { right };
+                }
+            }
+        }
+    }
+
+    // (значення, номер) листка, до якого потрапляє числовий рядок
+    fn leaf_numeric(&self, row: &[f64]) -> (f64, usize) {
+        let mut node = self;
+        loop {
+            match node {
+                RegressionNode::Leaf { value, id, .. } => return (*value, *id),
+                RegressionNode::Split { feature_index, condition, left, right, .. } => {
+                    node = if condition.goes_left_numeric(row[*feature_index]) { left } else { right };
+                }
+            }
+        }
+    }
+
+    // (сума SSE листків піддерева, кількість листків)
+    fn subtree_cost(&self) -> (f64, usize) {
+        match self {
+            RegressionNode::Leaf { sse, .. } => (*sse, 1),
+            RegressionNode::Split { left, right, .. } => {
+                let (l_sse, l_leaves) = left.subtree_cost();
+                let (r_sse, r_leaves) = right.subtree_cost();
+                (l_sse + r_sse, l_leaves + r_leaves)
+            }
+        }
+    }
+
+    // Найменше значення g(t) = (R(t) - R(T_t)) / (|T_t| - 1) серед внутрішніх вузлів (weakest link)
+    fn weakest_link(&self) -> f64 {
+        match self {
+            RegressionNode::Leaf { .. } => f64::INFINITY,
+            RegressionNode::Split { left, right, sse, .. } => {
+                let (subtree_sse, leaves) = self.subtree_cost();
+                let g = (sse - subtree_sse) / (leaves as f64 - 1.0);
+                g.min(left.weakest_link()).min(right.weakest_link())
+            }
+        }
+    }
+
+    // Перетворює у листки всі вузли з g(t) <= alpha
+    fn prune_weakest(&mut self, alpha: f64) {
+        if let RegressionNode::Split { left, right, sse, value, n_samples, .. } = self {
+            left.prune_weakest(alpha);
+            right.prune_weakest(alpha);
+            let (l_sse, l_leaves) = left.subtree_cost();
+            let (r_sse, r_leaves) = right.subtree_cost();
+            let g = (*sse - (l_sse + r_sse)) / ((l_leaves + r_leaves) as f64 - 1.0);
+            if g <= alpha {
+                *self = RegressionNode::Leaf { value: *value, n_samples: *n_samples, sse: *sse, id: 0 };
+            }
+        }
+    }
+
+    fn number_leaves(&mut self, next_id: &mut usize) {
+        match self {
+            RegressionNode::Leaf { id, .. } => {
+                *id = *next_id;
+                *next_id += 1;
+            }
+            RegressionNode::Split { left, right, .. } => {
+                left.number_leaves(next_id);
+                right.number_leaves(next_id);
+            }
+        }
+    }
+
+    fn assign_leaf_values(&mut self, values: &[f64]) {
+        match self {
+            RegressionNode::Leaf { value, id, .. } => *value = values[*id],
+            RegressionNode::Split { left, right, .. } => {
+                left.assign_leaf_values(values);
+                right.assign_leaf_values(values);
+            }
+        }
+    }
+
+    fn count_leaves(&self) -> usize {
+        self.subtree_cost().1
+    }
+
+    fn depth(&self) -> usize {
+        match self {
+            RegressionNode::Leaf { .. } => 0,
+            RegressionNode::Split { left, right, .. } => 1 + left.depth().max(right.depth()),
+        }
+    }
+
+    pub fn print_tree(&self, feature_names: &[&str], indent: &str) {
+        match self {
+            RegressionNode::Leaf { value, n_samples, .. } => {
+                println!("{}└── [Leaf] Value: {:.4} (samples: {})", indent, value, n_samples);
+            }
+            RegressionNode::Split { feature_index, condition, left, right, n_samples, .. } => {
+                println!("{}└── [Decision] {} (samples: {})", indent, condition.describe(feature_names[*feature_index]), n_samples);
+                println!("{}    ├── True:", indent);
+                left.print_tree(feature_names, &format!("{}    │   ", indent));
+                println!("{}    ├── False:", indent);
+                right.print_tree(feature_names, &format!("{}    │   ", indent));
+            }
+        }
+    }
+
+    // Прямий обхід: "leaf <значення>", "split <ознака> <поріг>" або "split_in <ознака> <кількість>"
+    // з рядками "in <категорія>" (значення - решта рядка), після вузла розбиття - ліве і праве піддерева
+    fn write_text(&self, out: &mut String) {
+        match self {
+            RegressionNode::Leaf { value, .. } => out.push_str(&format!("leaf {value}\n")),
+            RegressionNode::Split { feature_index, condition, left, right, .. } => {
+                match condition {
+                    Condition::Threshold(t) => out.push_str(&format!("split {feature_index} {t}\n")),
+                    Condition::Categories(set) => {
+                        let mut values: Vec<&str> = set.iter().map(|v| v.as_str()).collect();
+                        values.sort();
+                        out.push_str(&format!("split_in {feature_index} {}\n", values.len()));
+                        for value in values {
+                            out.push_str(&format!("in {value}\n"));
+                        }
+                    }
+                }
+                left.write_text(out);
+                right.write_text(out);
+            }
+        }
+    }
+
+    // Статистики вузлів (n_samples, sse) не зберігаються - відновлене дерево придатне лише для прогнозу
+    fn read_text<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<RegressionNode, Box<dyn Error>> {
+        let line = lines.next().ok_or("дерево обірвалося")?;
+        let mut parts = line.splitn(3, ' ');
+        let (kind, first, rest) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default(), parts.next());
+        let condition = match (kind, rest) {
+            ("leaf", None) => return Ok(RegressionNode::Leaf { value: first.parse()?, n_samples: 0, sse: 0.0, id: 0 }),
+            ("split", Some(threshold)) => Condition::Threshold(threshold.parse()?),
+            ("split_in", Some(count)) => {
+                let categories = (0..count.parse::<usize>()?)
+                    .map(|_| {
+                        let line = lines.next().ok_or("дерево обірвалося")?;
+                        line.strip_prefix("in ").map(|value| value.to_string()).ok_or(format!("очікувався рядок in, отримано: {line}"))
+                    })
+                    .collect::<Result<HashSet<String>, _>>()?;
+                Condition::Categories(categories)
+            }
+            _ => return Err(format!("некоректний вузол дерева: {line}").into()),
+        };
+        let left = Box::new(RegressionNode::read_text(lines)?);
+        let right = Box::new(RegressionNode::read_text(lines)?);
+        Ok(RegressionNode::Split { feature_index: first.parse()?, condition, left, right, value: 0.0, n_samples: 0, sse: 0.0 })
+    }
+
+    fn write_dot(&self, feature_names: &[&str], next_id: &mut usize, out: &mut String) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        match self {
+            RegressionNode::Leaf { value, n_samples, .. } => {
+                out.push_str(&format!("    {} [label=\"value = {:.4}\\nsamples = {}\", shape=box];\n", id, value, n_samples));
+            }
+            RegressionNode::Split { feature_index, condition, left, right, n_samples, .. } => {
+                let label = condition.describe(feature_names[*feature_index]).replace('"', "\\\"");
+                out.push_str(&format!("    {} [label=\"{}\\nsamples = {}\"];\n", id, label, n_samples));
+                let left_id = left.write_dot(feature_names, next_id, out);
+                out.push_str(&format!("    {} -> {} [label=\"True\"];\n", id, left_id));
+                let right_id = right.write_dot(feature_names, next_id, out);
+                out.push_str(&format!("    {} -> {} [label=\"False\"];\n", id, right_id));
+            }
+        }
+        id
+    }
+}
+
+// CART-дерево регресії: розбиття за зменшенням дисперсії (MSE)
+pub struct DecisionTreeRegressor {
+    root: RegressionNode,
+    max_depth: Option<usize>,
+    min_samples_leaf: usize,
+    numeric_features: Vec<bool>,
+    feature_gains: Vec<f64>,
+}
+
+struct BestSplit {
+    feature_index: usize,
+    condition: Condition,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    gain: f64,
+}
+
+fn mean_and_sse(y: &[f64], indices: &[usize]) -> (f64, f64) {
+    let n = indices.len() as f64;
+    let mean = indices.iter().map(|&i| y[i]).sum::<f64>() / n;
+    let sse = indices.iter().map(|&i| (y[i] - mean).powi(2)).sum();
+    (mean, sse)
+}
+
+impl Default for DecisionTreeRegressor {
+    fn default() -> Self {
+        DecisionTreeRegressor::new()
+    }
+}
+
+impl DecisionTreeRegressor {
+    pub fn new() -> Self {
+        DecisionTreeRegressor {
+            root: RegressionNode::Leaf { value: 0.0, n_samples: 0, sse: 0.0, id: 0 },
+            max_depth: None,
+            min_samples_leaf: 1,
+            numeric_features: Vec::new(),
+            feature_gains: Vec::new(),
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf.max(1);
+        self
+    }
+
+    // Ознаки зі значеннями, які всі парсяться як f64, вважаються числовими, решта - категоріальними
+    pub fn fit(&mut self, x: &[Vec<String>], y: &[f64]) {
+        let num_features = x[0].len();
+        self.numeric_features = (0..num_features)
+            .map(|fi| x.iter().all(|row| row[fi].parse::<f64>().is_ok()))
+            .collect();
+        let rows: Vec<Vec<Value>> = x.iter().map(|row| self.convert_row(row)).collect();
+        self.fit_values(&rows, y);
+    }
+
+    pub fn fit_numeric(&mut self, x: &[Vec<f64>], y: &[f64]) {
+        self.numeric_features = vec![true; x[0].len()];
+        let rows: Vec<Vec<Value>> = x.iter()
+            .map(|row| row.iter().map(|&v| Value::Numeric(v)).collect())
+            .collect();
+        self.fit_values(&rows, y);
+    }
+
+    fn fit_values(&mut self, x: &[Vec<Value>], y: &[f64]) {
+        self.feature_gains = vec![0.0; x[0].len()];
+        let all_indices: Vec<usize> = (0..y.len()).collect();
+        self.root = self.build_tree(x, y, &all_indices, 0);
+        self.root.number_leaves(&mut 0);
+    }
+
+    fn convert_row(&self, row: &[String]) -> Vec<Value> {
+        row.iter()
+            .enumerate()
+            .map(|(fi, v)| {
+                if self.numeric_features.get(fi).copied().unwrap_or(false) {
+                    Value::Numeric(v.parse::<f64>().unwrap_or(f64::NAN))
+                } else {
+                    Value::Categorical(v.clone())
+                }
+            })
+            .collect()
+    }
+
+    fn build_tree(&mut self, x: &[Vec<Value>], y: &[f64], indices: &[usize], depth: usize) -> RegressionNode {
+        let (value, sse) = mean_and_sse(y, indices);
+        let n_samples = indices.len();
+        let leaf = RegressionNode::Leaf { value, n_samples, sse, id: 0 };
+
+        // Передчасна зупинка: максимальна глибина, замало прикладів або чистий вузол
+        if self.max_depth.is_some_and(|d| depth >= d) || n_samples < 2 * self.min_samples_leaf || sse <= 1e-12 {
+            return leaf;
+        }
+
+        let best = match self.find_best_split(x, y, indices, sse) {
+            Some(best) => best,
+            None => return leaf,
+        };
+
+        self.feature_gains[best.feature_index] += best.gain / y.len() as f64;
+        let left = self.build_tree(x, y, &best.left, depth + 1);
+        let right = self.build_tree(x, y, &best.right, depth + 1);
+        RegressionNode::Split {
+            feature_index: best.feature_index,
+            condition: best.condition,
+            left: Box::new(left),
+            right: Box::new(right),
+            value,
+            n_samples,
+            sse,
+        }
+    }
+
+    fn find_best_split(&self, x: &[Vec<Value>], y: &[f64], indices: &[usize], parent_sse: f64) -> Option<BestSplit> {
+        let mut best: Option<BestSplit> = None;
+        let mut best_gain = 1e-12;
+        for (feature, &is_numeric) in self.numeric_features.iter().enumerate() {
+            // Упорядкувати приклади: числові - за значенням, категоріальні - за середнім таргетом категорії
+            // (для регресії це дає оптимальний бінарний поділ категорій, Breiman et al.)
+            let mut ordered: Vec<(f64, usize)> = if is_numeric {
+                indices.iter()
+                    .filter_map(|&i| match &x[i][feature] {
+                        Value::Numeric(v) if !v.is_nan() => Some((*v, i)),
+                        _ => None,
+                    })
+                    .collect()
+            } else {
+                let mut sums: HashMap<&String, (f64, usize)> = HashMap::new();
+                for &i in indices {
+                    if let Value::Categorical(v) = &x[i][feature] {
+                        let entry = sums.entry(v).or_insert((0.0, 0));
+                        entry.0 += y[i];
+                        entry.1 += 1;
+                    }
+                }
+                indices.iter()
+                    .filter_map(|&i| match &x[i][feature] {
+                        Value::Categorical(v) => {
+                            let (sum, count) = sums[v];
+                            Some((sum / count as f64, i))
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            };
+            if ordered.len() < indices.len() {
+                // пропущені значення - ознака не розглядається для цього вузла
+                continue;
+            }
+            ordered.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+            let total_sum: f64 = ordered.iter().map(|&(_, i)| y[i]).sum();
+            let total_sq: f64 = ordered.iter().map(|&(_, i)| y[i] * y[i]).sum();
+            let n = ordered.len();
+            let mut left_sum = 0.0;
+            let mut left_sq = 0.0;
+            for pos in 0..n - 1 {
+                let yi = y[ordered[pos].1];
+                left_sum += yi;
+                left_sq += yi * yi;
+                let n_left = pos + 1;
+                let n_right = n - n_left;
+                if ordered[pos].0 == ordered[pos + 1].0 || n_left < self.min_samples_leaf || n_right < self.min_samples_leaf {
+                    continue;
+                }
+                let right_sum = total_sum - left_sum;
+                let right_sq = total_sq - left_sq;
+                let child_sse = (left_sq - left_sum * left_sum / n_left as f64) + (right_sq - right_sum * right_sum / n_right as f64);
+                let gain = parent_sse - child_sse;
+                if gain > best_gain {
+                    best_gain = gain;
+                    let condition = if is_numeric {
+                        Condition::Threshold((ordered[pos].0 + ordered[pos + 1].0) / 2.0)
+                    } else {
+                        let categories = ordered[..=pos].iter()
+                            .filter_map(|&(_, i)| match &x[i][feature] {
+                                Value::Categorical(v) => Some(v.clone()),
+                                _ => None,
+                            })
+                            .collect();
+                        Condition::Categories(categories)
+                    };
+                    best = Some(BestSplit {
+                        feature_index: feature,
+                        condition,
+                        left: ordered[..=pos].iter().map(|&(_, i)| i).collect(),
+                        right: ordered[pos + 1..].iter().map(|&(_, i)| i).collect(),
+                        gain,
+                    });
+                }
+            }
+        }
+        best
+    }
+
+    pub fn predict(&self, data: &[Vec<String>]) -> Vec<f64> {
+        data.iter()
+            .map(|row| self.root.predict(&self.convert_row(row)))
+            .collect()
+    }
+
+    pub fn predict_numeric(&self, data: &[Vec<f64>]) -> Vec<f64> {
+        data.iter().map(|row| self.predict_one_numeric(row)).collect()
+    }
+
+    // Передбачення для одного числового рядка (без копіювання рядка)
+    pub fn predict_one_numeric(&self, row: &[f64]) -> f64 {
+        self.root.leaf_numeric(row).0
+    }
+
+    // Номери листків для кожного прикладу (див. set_leaf_values)
+    pub fn apply_numeric(&self, data: &[Vec<f64>]) -> Vec<usize> {
+        data.iter().map(|row| self.root.leaf_numeric(row).1).collect()
+    }
+
+    // Рядок "tree" з ознаками (1 - числова), потім вузли у прямому порядку
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("tree");
+        for &numeric in &self.numeric_features {
+            text.push_str(if numeric { " 1" } else { " 0" });
+        }
+        text.push('\n');
+        self.root.write_text(&mut text);
+        text
+    }
+
+    // Читає одне дерево, записане to_text, з початку lines
+    pub fn from_lines<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<Self, Box<dyn Error>> {
+        let header = lines.next().ok_or("немає рядка tree")?;
+        let flags = header.strip_prefix("tree").ok_or(format!("очікувався рядок tree, отримано: {header}"))?;
+        let mut tree = DecisionTreeRegressor::new();
+        tree.numeric_features = flags.split_whitespace().map(|flag| flag == "1").collect();
+        tree.feature_gains = vec![0.0; tree.numeric_features.len()];
+        tree.root = RegressionNode::read_text(lines)?;
+        tree.root.number_leaves(&mut 0);
+        Ok(tree)
+    }
+
+    // Замінити значення листків (наприклад, кроком Ньютона у градієнтному бустингу)
+    pub fn set_leaf_values(&mut self, values: &[f64]) {
+        self.root.assign_leaf_values(values);
+    }
+
+    // Мінімальне cost-complexity відсікання (CART): зрізаються всі піддерева з g(t) <= alpha
+    pub fn prune(&mut self, alpha: f64) {
+        self.root.prune_weakest(alpha);
+        self.root.number_leaves(&mut 0);
+    }
+
+    // Послідовність ефективних alpha, при яких дерево втрачає гілки (для вибору alpha на валідації)
+    pub fn cost_complexity_path(&self) -> Vec<f64> {
+        let mut alphas = vec![0.0];
+        let mut tree = self.root.clone();
+        while let RegressionNode::Split { .. } = tree {
+            let alpha = tree.weakest_link();
+            tree.prune_weakest(alpha);
+            alphas.push(alpha);
+        }
+        alphas
+    }
+
+    pub fn n_leaves(&self) -> usize {
+        self.root.count_leaves()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.root.depth()
+    }
+
+    // Середньоквадратична похибка на навчальних даних, збережена у листках
+    pub fn training_mse(&self) -> f64 {
+        let n = self.root.n_samples();
+        if n == 0 {
+            return 0.0;
+        }
+        self.root.subtree_cost().0 / n as f64
+    }
+
+    // Важливість ознак за зменшенням SSE (нормалізована, сума = 1)
+    pub fn feature_importances(&self) -> Vec<f64> {
+        let total: f64 = self.feature_gains.iter().sum();
+        if total <= 0.0 {
+            return vec![0.0; self.feature_gains.len()];
+        }
+        self.feature_gains.iter().map(|g| g / total).collect()
+    }
+
+    pub fn print_tree(&self, feature_names: &[&str], indent: &str) {
+        self.root.print_tree(feature_names, indent);
+    }
+
+    // Експорт у формат Graphviz DOT
+    pub fn export_dot(&self, feature_names: &[&str]) -> String {
+        let mut out = String::from("digraph RegressionTree {\n    node [fontname=\"helvetica\"];\n");
+        let mut next_id = 0;
+        self.root.write_dot(feature_names, &mut next_id, &mut out);
+        out.push_str("}\n");
+        out
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(values: &[&str]) -> Vec<Vec<String>> {
+        values.iter().map(|value| vec![value.to_string()]).collect()
+    }
+
+    #[test]
+    fn splits_numeric_and_categorical_steps() {
+        let mut numeric = DecisionTreeRegressor::new();
+        numeric.fit(&column(&["1", "2", "3", "4", "5", "6", "7", "8"]), &[1.0, 1.0, 1.0, 1.0, 5.0, 5.0, 5.0, 5.0]);
+        assert_eq!(numeric.n_leaves(), 2);
+        assert_eq!(numeric.training_mse(), 0.0);
+        assert_eq!(numeric.predict(&column(&["4.4", "4.6"])), vec![1.0, 5.0]);
+
+        let mut categorical = DecisionTreeRegressor::new();
+        categorical.fit(&column(&["a", "b", "c", "a", "b", "c"]), &[1.0, 5.0, 1.0, 1.0, 5.0, 1.0]);
+        assert_eq!(categorical.n_leaves(), 2);
+        assert_eq!(categorical.predict(&column(&["c", "b"])), vec![1.0, 5.0]);
+    }
+
+    #[test]
+    fn pruning_path_ends_at_the_root() {
+        let x = column(&["1", "2", "3", "4", "5", "6", "7", "8"]);
+        let y = [1.0, 2.0, 1.5, 1.0, 5.0, 6.0, 5.5, 9.0];
+        let mut tree = DecisionTreeRegressor::new().with_min_samples_leaf(2);
+        tree.fit(&x, &y);
+        let path = tree.cost_complexity_path();
+        assert!(path.windows(2).all(|pair| pair[0] <= pair[1]));
+        tree.prune(*path.last().unwrap());
+        assert_eq!(tree.n_leaves(), 1);
+        assert_eq!(tree.predict(&column(&["3"])), vec![y.iter().sum::<f64>() / 8.0]);
+    }
+
+    #[test]
+    fn leaf_ids_follow_pruning() {
+        let x: Vec<Vec<f64>> = (1..=8).map(|v| vec![v as f64]).collect();
+        let y = [1.0, 2.0, 1.5, 1.0, 5.0, 6.0, 5.5, 9.0];
+        let mut tree = DecisionTreeRegressor::new();
+        tree.fit_numeric(&x, &y);
+        assert_eq!(tree.apply_numeric(&x), (0..8).collect::<Vec<usize>>());
+
+        tree.prune(1.0);
+        let leaves = tree.apply_numeric(&x);
+        assert!(tree.n_leaves() < 8);
+        assert_eq!(leaves.iter().max(), Some(&(tree.n_leaves() - 1)));
+        assert!(leaves.windows(2).all(|pair| pair[0] <= pair[1]));
+        let values: Vec<f64> = (0..tree.n_leaves()).map(|leaf| leaf as f64 * 10.0).collect();
+        tree.set_leaf_values(&values);
+        let predictions: Vec<f64> = x.iter().map(|row| tree.predict_one_numeric(row)).collect();
+        assert_eq!(predictions, leaves.iter().map(|&leaf| leaf as f64 * 10.0).collect::<Vec<f64>>());
+    }
+
+    #[test]
+    fn text_round_trip_keeps_categories_with_separators() {
+        let x = column(&["a b", "c\td", "e", "a b", "c\td", "e"]);
+        let mut tree = DecisionTreeRegressor::new();
+        tree.fit(&x, &[1.0, 5.0, 9.0, 1.0, 5.0, 9.0]);
+        let text = tree.to_text();
+        let restored = DecisionTreeRegressor::from_lines(&mut text.lines()).unwrap();
+        assert_eq!(restored.to_text(), text);
+        assert_eq!(restored.predict(&x), tree.predict(&x));
+    }
+}
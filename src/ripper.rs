@@ -0,0 +1,353 @@
+use crate::decision_list::{candidate_conditions, predict_decision_list, print_decision_list, update_coverage, Condition, DecisionRule};
+use crate::dummy::ZeroRClassifier;
+use crate::util::Rng;
+
+// RIPPER (Cohen, 1995): класи від найрідшого до найчастішого, для кожного - набір правил IREP*
+// (ріст правила за FOIL gain на 2/3 прикладів, скорочення на решті 1/3), зупинка за довжиною опису (MDL),
+// потім кілька проходів оптимізації правил; найчастіший клас - клас за замовчуванням
+pub struct RipperClassifier {
+    optimizations: usize,
+    seed: u64,
+    rules: Vec<DecisionRule>,
+    default_class: String,
+}
+
+// Набір правил одного класу на час навчання; pos / neg - індекси прикладів цього та інших класів
+struct ClassRules<'a> {
+    x: &'a [Vec<String>],
+    pos: Vec<usize>,
+    neg: Vec<usize>,
+    possible_conditions: usize,
+    rules: Vec<Vec<Condition>>,
+}
+
+// Наскільки довжина опису може перевищити найменшу, перш ніж додавання правил зупиняється
+const MAX_DL_SURPLUS: f64 = 64.0;
+
+fn covers(conditions: &[Condition], row: &[String]) -> bool {
+    conditions.iter().all(|condition| condition.matches(row))
+}
+
+fn count_covered(conditions: &[Condition], x: &[Vec<String>], rows: &[usize]) -> usize {
+    rows.iter().filter(|&&i| covers(conditions, &x[i])).count()
+}
+
+// Біти на вибір k елементів з n, якщо кожен обрано з ймовірністю p
+fn subset_dl(n: f64, k: f64, p: f64) -> f64 {
+    let mut bits = 0.0;
+    if k > 0.0 {
+        bits -= k * p.log2();
+    }
+    if n > k {
+        bits -= (n - k) * (1.0 - p).log2();
+    }
+    bits
+}
+
+// Довжина опису правила з k умовами серед possible можливих (половина - поправка на надлишковість умов)
+fn theory_dl(k: usize, possible: usize) -> f64 {
+    if k == 0 {
+        return 0.0;
+    }
+    let k_bits = (k as f64).log2();
+    let length_bits = if k_bits > 1.0 { k_bits + 2.0 * k_bits.log2() } else { k_bits };
+    0.5 * (length_bits + subset_dl(possible as f64, k as f64, k as f64 / possible.max(k) as f64))
+}
+
+// Біти на помилки набору правил: хибнопозитивні серед покритих і хибнонегативні серед непокритих
+fn exceptions_dl(covered: usize, uncovered: usize, false_positives: usize, false_negatives: usize) -> f64 {
+    let mut bits = ((covered + uncovered + 1) as f64).log2();
+    if covered > 0 {
+        bits += subset_dl(covered as f64, false_positives as f64, false_positives as f64 / covered as f64);
+    }
+    if uncovered > 0 {
+        bits += subset_dl(uncovered as f64, false_negatives as f64, false_negatives as f64 / uncovered as f64);
+    }
+    bits
+}
+
+// Приріст інформації FOIL: p1 * (log2(p1 / (p1 + n1)) - log2(p0 / (p0 + n0)))
+fn foil_gain(p0: usize, n0: usize, p1: usize, n1: usize) -> f64 {
+    if p0 == 0 || p1 == 0 {
+        return 0.0;
+    }
+    let before = (p0 as f64 / (p0 + n0) as f64).log2();
+    let after = (p1 as f64 / (p1 + n1) as f64).log2();
+    p1 as f64 * (after - before)
+}
+
+// Ріст правила: додаються умови з найбільшим FOIL gain, доки правило покриває негативні приклади
+fn grow_rule(initial: &[Condition], x: &[Vec<String>], pos: &[usize], neg: &[usize]) -> Vec<Condition> {
+    let mut conditions = initial.to_vec();
+    let mut pos: Vec<usize> = pos.iter().copied().filter(|&i| covers(&conditions, &x[i])).collect();
+    let mut neg: Vec<usize> = neg.iter().copied().filter(|&i| covers(&conditions, &x[i])).collect();
+    while !neg.is_empty() && !pos.is_empty() {
+        let mut best: Option<(Condition, f64)> = None;
+        for condition in candidate_conditions(x, &pos, &conditions) {
+            let p1 = pos.iter().filter(|&&i| condition.matches(&x[i])).count();
+            let n1 = neg.iter().filter(|&&i| condition.matches(&x[i])).count();
+            let gain = foil_gain(pos.len(), neg.len(), p1, n1);
+            if gain > 0.0 && best.as_ref().is_none_or(|(_, best_gain)| gain > *best_gain) {
+                best = Some((condition, gain));
+            }
+        }
+        let Some((condition, _)) = best else { break };
+        pos.retain(|&i| condition.matches(&x[i]));
+        neg.retain(|&i| condition.matches(&x[i]));
+        conditions.push(condition);
+    }
+    conditions
+}
+
+// Скорочення правила: залишається префікс умов з найкращою оцінкою (при рівності - коротший);
+// None - оцінити неможливо (немає прикладів для скорочення), правило не змінюється
+fn prune_rule(mut conditions: Vec<Condition>, score: impl Fn(&[Condition]) -> Option<f64>) -> Vec<Condition> {
+    let mut best: Option<(usize, f64)> = None;
+    for length in 1..=conditions.len() {
+        if let Some(value) = score(&conditions[..length]) {
+            if best.is_none_or(|(_, best_value)| value > best_value) {
+                best = Some((length, value));
+            }
+        }
+    }
+    if let Some((length, _)) = best {
+        conditions.truncate(length);
+    }
+    conditions
+}
+
+// Випадковий поділ прикладів: 2/3 для росту правила, 1/3 для скорочення
+fn split_grow_prune(rows: &[usize], rng: &mut Rng) -> (Vec<usize>, Vec<usize>) {
+    let mut shuffled = rows.to_vec();
+    rng.shuffle(&mut shuffled);
+    let grow_size = (shuffled.len() * 2).div_ceil(3);
+    let prune = shuffled.split_off(grow_size);
+    (shuffled, prune)
+}
+
+impl ClassRules<'_> {
+    fn covered_by_any(&self, rules: &[Vec<Condition>], row: &[String]) -> bool {
+        rules.iter().any(|rule| covers(rule, row))
+    }
+
+    // Довжина опису набору правил разом з його помилками на прикладах класу
+    fn description_length(&self, rules: &[Vec<Condition>]) -> f64 {
+        let covered_pos = self.pos.iter().filter(|&&i| self.covered_by_any(rules, &self.x[i])).count();
+        let covered_neg = self.neg.iter().filter(|&&i| self.covered_by_any(rules, &self.x[i])).count();
+        let theory: f64 = rules.iter().map(|rule| theory_dl(rule.len(), self.possible_conditions)).sum();
+        let uncovered = self.pos.len() + self.neg.len() - covered_pos - covered_neg;
+        theory + exceptions_dl(covered_pos + covered_neg, uncovered, covered_neg, self.pos.len() - covered_pos)
+    }
+
+    // Приклади, які не покриває жодне з правил rules
+    fn uncovered(&self, rules: &[Vec<Condition>], rows: &[usize]) -> Vec<usize> {
+        rows.iter().copied().filter(|&i| !self.covered_by_any(rules, &self.x[i])).collect()
+    }
+
+    // IREP*: нові правила для ще не покритих позитивних прикладів
+    fn cover(&mut self, rng: &mut Rng) {
+        let x = self.x;
+        let mut pos = self.uncovered(&self.rules, &self.pos);
+        let mut neg = self.uncovered(&self.rules, &self.neg);
+        let mut best_dl = self.description_length(&self.rules);
+        while !pos.is_empty() {
+            let (grow_pos, prune_pos) = split_grow_prune(&pos, rng);
+            let (grow_neg, prune_neg) = split_grow_prune(&neg, rng);
+            let rule = grow_rule(&[], x, &grow_pos, &grow_neg);
+            // (p + 1) / (p + n + 2) замість (p - n) / (p + n), як у Weka JRip: оцінка визначена й тоді,
+            // коли правило не покриває жодного прикладу для скорочення
+            let rule = prune_rule(rule, |conditions| {
+                let p = count_covered(conditions, x, &prune_pos) as f64;
+                let n = count_covered(conditions, x, &prune_neg) as f64;
+                Some((p + 1.0) / (p + n + 2.0))
+            });
+            if rule.is_empty() || count_covered(&rule, x, &pos) == 0 {
+                break;
+            }
+            // правило, яке на прикладах для скорочення частіше помиляється, ніж вгадує, вже не корисне
+            let p = count_covered(&rule, x, &prune_pos);
+            let n = count_covered(&rule, x, &prune_neg);
+            if n > p {
+                break;
+            }
+            self.rules.push(rule);
+            let dl = self.description_length(&self.rules);
+            if dl > best_dl + MAX_DL_SURPLUS {
+                self.rules.pop();
+                break;
+            }
+            best_dl = best_dl.min(dl);
+            let rule = &self.rules[self.rules.len() - 1];
+            pos.retain(|&i| !covers(rule, &x[i]));
+            neg.retain(|&i| !covers(rule, &x[i]));
+        }
+    }
+
+    // Вилучення правил (з кінця), без яких довжина опису менша
+    fn reduce_description_length(&mut self) {
+        for i in (0..self.rules.len()).rev() {
+            let mut without = self.rules.clone();
+            without.remove(i);
+            if self.description_length(&without) < self.description_length(&self.rules) {
+                self.rules = without;
+            }
+        }
+    }
+
+    // Оптимізація: для кожного правила будуються заміна (з нуля) та ревізія (доповнення правила);
+    // обидві скорочуються за точністю всього набору, залишається варіант з найменшою довжиною опису
+    fn optimize(&mut self, rng: &mut Rng) {
+        let x = self.x;
+        for i in 0..self.rules.len() {
+            let pos = self.uncovered(&self.rules[..i], &self.pos);
+            let neg = self.uncovered(&self.rules[..i], &self.neg);
+            let (grow_pos, prune_pos) = split_grow_prune(&pos, rng);
+            let (grow_neg, prune_neg) = split_grow_prune(&neg, rng);
+            let ruleset_accuracy = |candidate: &[Condition]| {
+                let total = prune_pos.len() + prune_neg.len();
+                let mut trial = self.rules.clone();
+                trial[i] = candidate.to_vec();
+                let true_positives = prune_pos.iter().filter(|&&j| self.covered_by_any(&trial, &x[j])).count();
+                let true_negatives = prune_neg.iter().filter(|&&j| !self.covered_by_any(&trial, &x[j])).count();
+                (total > 0).then(|| (true_positives + true_negatives) as f64 / total as f64)
+            };
+            let replacement = prune_rule(grow_rule(&[], x, &grow_pos, &grow_neg), ruleset_accuracy);
+            let revision = prune_rule(grow_rule(&self.rules[i], x, &grow_pos, &grow_neg), ruleset_accuracy);
+
+            let mut best_rule = self.rules[i].clone();
+            let mut best_dl = self.description_length(&self.rules);
+            for variant in [replacement, revision] {
+                if variant.is_empty() {
+                    continue;
+                }
+                let mut trial = self.rules.clone();
+                trial[i] = variant.clone();
+                let dl = self.description_length(&trial);
+                if dl < best_dl {
+                    best_dl = dl;
+                    best_rule = variant;
+                }
+            }
+            self.rules[i] = best_rule;
+        }
+    }
+}
+
+impl Default for RipperClassifier {
+    fn default() -> Self {
+        RipperClassifier::new()
+    }
+}
+
+impl RipperClassifier {
+    pub fn new() -> Self {
+        RipperClassifier { optimizations: 2, seed: 1, rules: Vec::new(), default_class: String::new() }
+    }
+
+    // Кількість проходів оптимізації (RIPPERk); 0 - лише IREP*
+    pub fn with_optimizations(mut self, optimizations: usize) -> Self {
+        self.optimizations = optimizations;
+        self
+    }
+
+    // Зерно для поділу прикладів на частини росту та скорочення
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn fit(&mut self, x: &[Vec<String>], y: &[String]) {
+        let mut zero_r = ZeroRClassifier::new();
+        zero_r.fit(y);
+        self.default_class = zero_r.default_class().to_string();
+
+        // Класи від найрідшого; найчастіший не отримує правил
+        let mut classes: Vec<&String> = y.iter().filter(|label| **label != self.default_class).collect();
+        classes.sort();
+        classes.dedup();
+        classes.sort_by_key(|class| y.iter().filter(|label| label == class).count());
+
+        let all_rows: Vec<usize> = (0..x.len()).collect();
+        let possible_conditions = candidate_conditions(x, &all_rows, &[]).len();
+        let mut rng = Rng::new(self.seed);
+        let mut remaining = all_rows;
+        self.rules.clear();
+        for class in classes {
+            let (pos, neg): (Vec<usize>, Vec<usize>) = remaining.iter().partition(|&&i| y[i] == *class);
+            if pos.is_empty() {
+                continue;
+            }
+            let mut class_rules = ClassRules { x, pos, neg, possible_conditions, rules: Vec::new() };
+            class_rules.cover(&mut rng);
+            class_rules.reduce_description_length();
+            for _ in 0..self.optimizations {
+                class_rules.optimize(&mut rng);
+                class_rules.cover(&mut rng);
+                class_rules.reduce_description_length();
+            }
+            // приклади, покриті правилами класу, далі не розглядаються
+            remaining.retain(|&i| !class_rules.covered_by_any(&class_rules.rules, &x[i]));
+            self.rules.extend(class_rules.rules.into_iter().map(|conditions| DecisionRule::new(conditions, class.clone())));
+        }
+        update_coverage(&mut self.rules, x, y);
+    }
+
+    pub fn predict(&self, data: &[Vec<String>]) -> Vec<String> {
+        predict_decision_list(&self.rules, &self.default_class, data)
+    }
+
+    pub fn rules(&self) -> &[DecisionRule] {
+        &self.rules
+    }
+
+    pub fn default_class(&self) -> &str {
+        &self.default_class
+    }
+
+    pub fn print_rules(&self, feature_names: &[&str]) {
+        print_decision_list(&self.rules, &self.default_class, feature_names);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Номінальний weather з Witten & Frank (14 прикладів)
+    fn weather() -> (Vec<Vec<String>>, Vec<String>) {
+        ["sunny hot high false no", "sunny hot high true no", "overcast hot high false yes", "rainy mild high false yes",
+         "rainy cool normal false yes", "rainy cool normal true no", "overcast cool normal true yes", "sunny mild high false no",
+         "sunny cool normal false yes", "rainy mild normal false yes", "sunny mild normal true yes", "overcast mild high true yes",
+         "overcast hot normal false yes", "rainy mild high true no"]
+            .iter()
+            .map(|row| {
+                let mut values: Vec<String> = row.split_whitespace().map(|value| value.to_string()).collect();
+                let class = values.pop().unwrap();
+                (values, class)
+            })
+            .unzip()
+    }
+
+    const NAMES: [&str; 4] = ["outlook", "temperature", "humidity", "windy"];
+
+    // Як у Weka JRip: два правила для "no", решта - клас за замовчуванням "yes" (порядок умов залежить від seed)
+    #[test]
+    fn weather_rules_match_the_textbook() {
+        let (x, y) = weather();
+        for seed in 0..5 {
+            let mut model = RipperClassifier::new().with_seed(seed);
+            model.fit(&x, &y);
+            let mut rules: Vec<String> = model.rules().iter()
+                .map(|rule| {
+                    let mut conditions: Vec<String> = rule.conditions.iter().map(|c| c.describe(&NAMES)).collect();
+                    conditions.sort();
+                    format!("{} -> {}", conditions.join(" AND "), rule.class)
+                })
+                .collect();
+            rules.sort();
+            assert_eq!(rules, vec!["humidity = high AND outlook = sunny -> no", "outlook = rainy AND windy = true -> no"], "seed {seed}");
+            assert_eq!(model.default_class(), "yes");
+            assert_eq!(model.predict(&x), y);
+        }
+    }
+}
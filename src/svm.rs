@@ -0,0 +1,421 @@
+use std::error::Error;
+use crate::pipeline::Classifier;
+use crate::preprocessing::{parse_values_line, values_line};
+use crate::util::{dot, Rng};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Kernel {
+    Linear,
+    // exp(-gamma * |a - b|^2)
+    Rbf(f64),
+    // (gamma * a·b + coef0)^degree
+    Polynomial { degree: u32, gamma: f64, coef0: f64 },
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SvmSolver {
+    // стохастичний субградієнтний спуск Pegasos (Shalev-Shwartz et al., 2007), лише лінійне ядро
+    Pegasos,
+    // двоїстий координатний спуск (Hsieh et al., 2008), лише лінійне ядро
+    CoordinateDescent,
+    // SMO з вибором пари за порушенням умов ККТ (як у LIBSVM), будь-яке ядро
+    Smo,
+}
+
+// Бінарна машина: f(x) = w·x + b для лінійного ядра або sum coef_i * K(sv_i, x) + b
+struct BinaryMachine {
+    bias: f64,
+    weights: Vec<f64>,
+    support_vectors: Vec<Vec<f64>>,
+    dual_coef: Vec<f64>,  // alpha_i * y_i
+}
+
+// Метод опорних векторів із м'яким зазором (параметр C); для двох класів - одна машина (додатний клас - classes()[1],
+// для більшої кількості - "один проти решти" з вибором класу з найбільшим значенням f(x).
+// Лінійні розв'язувачі з нелінійним ядром не працюють, тому для ядер RBF та поліноміального завжди використовується SMO.
+pub struct SvmClassifier {
+    kernel: Kernel,
+    solver: SvmSolver,
+    c: f64,
+    max_iter: usize,
+    tolerance: f64,
+    seed: u64,
+    classes: Vec<String>,
+    machines: Vec<BinaryMachine>,
+}
+
+impl Kernel {
+    pub fn compute(&self, a: &[f64], b: &[f64]) -> f64 {
+        match *self {
+            Kernel::Linear => dot(a, b),
+            Kernel::Rbf(gamma) => (-gamma * a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>()).exp(),
+            Kernel::Polynomial { degree, gamma, coef0 } => (gamma * dot(a, b) + coef0).powi(degree as i32),
+        }
+    }
+
+    fn to_text(self) -> String {
+        match self {
+            Kernel::Linear => "linear".to_string(),
+            Kernel::Rbf(gamma) => format!("rbf {gamma}"),
+            Kernel::Polynomial { degree, gamma, coef0 } => format!("poly {degree} {gamma} {coef0}"),
+        }
+    }
+}
+
+impl BinaryMachine {
+    fn decision(&self, kernel: Kernel, row: &[f64]) -> f64 {
+        if self.support_vectors.is_empty() {
+            self.bias + dot(&self.weights, row)
+        } else {
+            self.bias + self.support_vectors.iter().zip(&self.dual_coef).map(|(sv, coef)| coef * kernel.compute(sv, row)).sum::<f64>()
+        }
+    }
+}
+
+impl SvmClassifier {
+    pub fn new(c: f64) -> Self {
+        SvmClassifier {
+            kernel: Kernel::Linear,
+            solver: SvmSolver::CoordinateDescent,
+            c,
+            max_iter: 200,
+            tolerance: 1e-3,
+            seed: 1,
+            classes: Vec::new(),
+            machines: Vec::new(),
+        }
+    }
+
+    pub fn with_kernel(mut self, kernel: Kernel) -> Self {
+        self.kernel = kernel;
+        self
+    }
+
+    pub fn with_solver(mut self, solver: SvmSolver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    // Кількість проходів по даних (для SMO - обмеження max_iter * n кроків)
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    // Зерно для порядку прикладів у Pegasos та координатному спуску
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // Pegasos: мінімізація lambda/2 |w|^2 + середня hinge-втрата, lambda = 1 / (C n);
+    // вільний член - вага додаткової сталої ознаки 1 (регуляризується разом з рештою)
+    fn fit_pegasos(&self, x: &[Vec<f64>], y: &[f64]) -> BinaryMachine {
+        let n = x.len();
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        let lambda = 1.0 / (self.c * n.max(1) as f64);
+        let mut rng = Rng::new(self.seed);
+        let mut w = vec![0.0; dims + 1];
+        for t in 1..=self.max_iter * n {
+            let i = rng.gen_range(n);
+            let eta = 1.0 / (lambda * t as f64);
+            let margin = y[i] * (w[dims] + dot(&w[..dims], &x[i]));
+            w.iter_mut().for_each(|wj| *wj *= 1.0 - eta * lambda);
+            if margin < 1.0 {
+                w.iter_mut().zip(x[i].iter().chain(std::iter::once(&1.0))).for_each(|(wj, v)| *wj += eta * y[i] * v);
+            }
+        }
+        BinaryMachine { bias: w[dims], weights: w[..dims].to_vec(), support_vectors: Vec::new(), dual_coef: Vec::new() }
+    }
+
+    // Двоїстий координатний спуск для hinge-втрати: 0 <= alpha_i <= C, w = sum alpha_i y_i x_i
+    // (вільний член - вага сталої ознаки 1); зупинка, коли розкид проєктованого градієнта менший за tolerance
+    fn fit_coordinate_descent(&self, x: &[Vec<f64>], y: &[f64]) -> BinaryMachine {
+        let n = x.len();
+        let dims = x.first().map(|row| row.len()).unwrap_or(0);
+        let mut rng = Rng::new(self.seed);
+        let mut alpha = vec![0.0; n];
+        let mut w = vec![0.0; dims + 1];
+        let diagonal: Vec<f64> = x.iter().map(|row| dot(row, row) + 1.0).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        for _ in 0..self.max_iter {
+            rng.shuffle(&mut order);
+            let (mut max_pg, mut min_pg) = (f64::NEG_INFINITY, f64::INFINITY);
+            for &i in &order {
+                let gradient = y[i] * (w[dims] + dot(&w[..dims], &x[i])) - 1.0;
+                let projected = if alpha[i] <= 0.0 {
+                    gradient.min(0.0)
+                } else if alpha[i] >= self.c {
+                    gradient.max(0.0)
+                } else {
+                    gradient
+                };
+                max_pg = max_pg.max(projected);
+                min_pg = min_pg.min(projected);
+                if projected.abs() > 1e-12 {
+                    let old = alpha[i];
+                    alpha[i] = (old - gradient / diagonal[i]).clamp(0.0, self.c);
+                    let delta = (alpha[i] - old) * y[i];
+                    w.iter_mut().zip(x[i].iter().chain(std::iter::once(&1.0))).for_each(|(wj, v)| *wj += delta * v);
+                }
+            }
+            if max_pg - min_pg < self.tolerance {
+                break;
+            }
+        }
+        BinaryMachine { bias: w[dims], weights: w[..dims].to_vec(), support_vectors: Vec::new(), dual_coef: Vec::new() }
+    }
+
+    // SMO для двоїстої задачі min 1/2 a'Qa - sum a, Q_ij = y_i y_j K_ij, 0 <= a_i <= C, sum y_i a_i = 0.
+    // На кожному кроці - пара (i, j), що найбільше порушує умови ККТ (j - за другим порядком, як WSS2 у LIBSVM).
+    fn fit_smo(&self, x: &[Vec<f64>], y: &[f64], kernel_matrix: &[Vec<f64>]) -> BinaryMachine {
+        let n = x.len();
+        let c = self.c;
+        let mut alpha = vec![0.0; n];
+        let mut gradient = vec![-1.0; n];
+        let in_up = |alpha: &[f64], t: usize| (y[t] > 0.0 && alpha[t] < c) || (y[t] < 0.0 && alpha[t] > 0.0);
+        let in_low = |alpha: &[f64], t: usize| (y[t] > 0.0 && alpha[t] > 0.0) || (y[t] < 0.0 && alpha[t] < c);
+        for _ in 0..self.max_iter * n.max(1) {
+            let Some(i) = (0..n).filter(|&t| in_up(&alpha, t)).max_by(|&a, &b| (-y[a] * gradient[a]).total_cmp(&(-y[b] * gradient[b]))) else { break };
+            let m = -y[i] * gradient[i];
+            let lowest = (0..n).filter(|&t| in_low(&alpha, t)).map(|t| -y[t] * gradient[t]).fold(f64::INFINITY, f64::min);
+            if m - lowest < self.tolerance {
+                break;
+            }
+            // j: найбільше спадання цільової функції -b^2 / a серед порушників
+            let mut best: Option<(usize, f64)> = None;
+            for t in (0..n).filter(|&t| in_low(&alpha, t)) {
+                let b = m + y[t] * gradient[t];
+                if b <= 0.0 {
+                    continue;
+                }
+                let a = (kernel_matrix[i][i] + kernel_matrix[t][t] - 2.0 * kernel_matrix[i][t]).max(1e-12);
+                let decrease = -b * b / a;
+                if best.is_none_or(|(_, best_decrease)| decrease < best_decrease) {
+                    best = Some((t, decrease));
+                }
+            }
+            let Some((j, _)) = best else { break };
+
+            // крок t уздовж напрямку (a_i += y_i t, a_j -= y_j t) з обмеженнями 0 <= a <= C
+            let a = (kernel_matrix[i][i] + kernel_matrix[j][j] - 2.0 * kernel_matrix[i][j]).max(1e-12);
+            let b = m + y[j] * gradient[j];
+            let bound_i = if y[i] > 0.0 { c - alpha[i] } else { alpha[i] };
+            let bound_j = if y[j] > 0.0 { alpha[j] } else { c - alpha[j] };
+            let step = (b / a).min(bound_i).min(bound_j);
+            alpha[i] += y[i] * step;
+            alpha[j] -= y[j] * step;
+            alpha[i] = alpha[i].clamp(0.0, c);
+            alpha[j] = alpha[j].clamp(0.0, c);
+            for (k, g) in gradient.iter_mut().enumerate() {
+                *g += y[k] * step * (kernel_matrix[k][i] - kernel_matrix[k][j]);
+            }
+        }
+
+        // b = -y_t G_t для векторів усередині (0, C); якщо таких немає - середина допустимого відрізка
+        let free: Vec<f64> = (0..n).filter(|&t| alpha[t] > 0.0 && alpha[t] < c).map(|t| -y[t] * gradient[t]).collect();
+        let bias = if free.is_empty() {
+            let upper = (0..n).filter(|&t| in_up(&alpha, t)).map(|t| -y[t] * gradient[t]).fold(f64::NEG_INFINITY, f64::max);
+            let lower = (0..n).filter(|&t| in_low(&alpha, t)).map(|t| -y[t] * gradient[t]).fold(f64::INFINITY, f64::min);
+            if upper.is_finite() && lower.is_finite() { (upper + lower) / 2.0 } else { 0.0 }
+        } else {
+            free.iter().sum::<f64>() / free.len() as f64
+        };
+
+        let support: Vec<usize> = (0..n).filter(|&t| alpha[t] > 0.0).collect();
+        let dual_coef: Vec<f64> = support.iter().map(|&t| alpha[t] * y[t]).collect();
+        if self.kernel == Kernel::Linear {
+            // для лінійного ядра достатньо вектора ваг
+            let dims = x.first().map(|row| row.len()).unwrap_or(0);
+            let mut weights = vec![0.0; dims];
+            for (&t, coef) in support.iter().zip(&dual_coef) {
+                weights.iter_mut().zip(&x[t]).for_each(|(w, v)| *w += coef * v);
+            }
+            BinaryMachine { bias, weights, support_vectors: Vec::new(), dual_coef: Vec::new() }
+        } else {
+            BinaryMachine { bias, weights: Vec::new(), support_vectors: support.iter().map(|&t| x[t].clone()).collect(), dual_coef }
+        }
+    }
+
+    pub fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        let mut classes: Vec<String> = y.to_vec();
+        classes.sort();
+        classes.dedup();
+        let solver = if self.kernel == Kernel::Linear { self.solver } else { SvmSolver::Smo };
+        // матриця ядра спільна для всіх машин "один проти решти"
+        let kernel_matrix: Vec<Vec<f64>> = if solver == SvmSolver::Smo {
+            x.iter().map(|a| x.iter().map(|b| self.kernel.compute(a, b)).collect()).collect()
+        } else {
+            Vec::new()
+        };
+        let positives: Vec<&String> = if classes.len() <= 2 { classes.iter().skip(1).collect() } else { classes.iter().collect() };
+        self.machines = positives.into_iter()
+            .map(|positive| {
+                let targets: Vec<f64> = y.iter().map(|label| if label == positive { 1.0 } else { -1.0 }).collect();
+                match solver {
+                    SvmSolver::Pegasos => self.fit_pegasos(x, &targets),
+                    SvmSolver::CoordinateDescent => self.fit_coordinate_descent(x, &targets),
+                    SvmSolver::Smo => self.fit_smo(x, &targets, &kernel_matrix),
+                }
+            })
+            .collect();
+        self.classes = classes;
+    }
+
+    // Значення f(x) кожної машини
+    pub fn decision_function(&self, x: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        x.iter().map(|row| self.machines.iter().map(|machine| machine.decision(self.kernel, row)).collect()).collect()
+    }
+
+    pub fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        self.decision_function(x)
+            .into_iter()
+            .map(|scores| {
+                let best = if self.machines.len() == 1 {
+                    usize::from(scores[0] > 0.0).min(self.classes.len() - 1)
+                } else {
+                    (0..scores.len()).fold(0, |best, c| if scores[c] > scores[best] { c } else { best })
+                };
+                self.classes[best].clone()
+            })
+            .collect()
+    }
+
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    // Кількість опорних векторів кожної машини (для лінійного ядра ваги зберігаються замість векторів - 0)
+    pub fn n_support(&self) -> Vec<usize> {
+        self.machines.iter().map(|machine| machine.support_vectors.len()).collect()
+    }
+
+    // Ваги лінійних машин (порожні для нелінійних ядер)
+    pub fn coefficients(&self) -> Vec<&[f64]> {
+        self.machines.iter().map(|machine| machine.weights.as_slice()).collect()
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = text.lines().peekable();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+        let kernel = match header.as_slice() {
+            ["svm", "linear"] => Kernel::Linear,
+            ["svm", "rbf", gamma] => Kernel::Rbf(gamma.parse()?),
+            ["svm", "poly", degree, gamma, coef0] => Kernel::Polynomial { degree: degree.parse()?, gamma: gamma.parse()?, coef0: coef0.parse()? },
+            _ => return Err(format!("невідомий заголовок SVM: {:?}", header).into()),
+        };
+        let mut model = SvmClassifier::new(1.0).with_kernel(kernel);
+        while let Some(class) = lines.peek().and_then(|line| line.strip_prefix("class ")) {
+            model.classes.push(class.to_string());
+            lines.next();
+        }
+        while let Some(line) = lines.next() {
+            if line != "machine" {
+                return Err(format!("очікувався рядок machine, отримано: {line}").into());
+            }
+            let bias = parse_values_line(lines.next(), "bias")?.first().copied().ok_or("порожній bias")?;
+            let mut machine = BinaryMachine { bias, weights: Vec::new(), support_vectors: Vec::new(), dual_coef: Vec::new() };
+            if kernel == Kernel::Linear {
+                machine.weights = parse_values_line(lines.next(), "weights")?;
+            } else {
+                while lines.peek().is_some_and(|line| line.starts_with("sv ")) {
+                    let values = parse_values_line(lines.next(), "sv")?;
+                    let (coef, vector) = values.split_first().ok_or("порожній рядок sv")?;
+                    machine.dual_coef.push(*coef);
+                    machine.support_vectors.push(vector.to_vec());
+                }
+            }
+            model.machines.push(machine);
+        }
+        Ok(model)
+    }
+}
+
+impl Classifier for SvmClassifier {
+    fn fit(&mut self, x: &[Vec<f64>], y: &[String]) {
+        SvmClassifier::fit(self, x, y);
+    }
+
+    fn predict(&self, x: &[Vec<f64>]) -> Vec<String> {
+        SvmClassifier::predict(self, x)
+    }
+
+    // Заголовок "svm <ядро>", класи, потім для кожної машини bias і ваги або опорні вектори (коефіцієнт, вектор)
+    fn to_text(&self) -> String {
+        let mut text = format!("svm {}\n", self.kernel.to_text());
+        for class in &self.classes {
+            text.push_str(&format!("class {class}\n"));
+        }
+        for machine in &self.machines {
+            text.push_str(&format!("machine\n{}\n", values_line("bias", &[machine.bias])));
+            if self.kernel == Kernel::Linear {
+                text.push_str(&values_line("weights", &machine.weights));
+                text.push('\n');
+            }
+            for (coef, vector) in machine.dual_coef.iter().zip(&machine.support_vectors) {
+                let values: Vec<f64> = std::iter::once(*coef).chain(vector.iter().copied()).collect();
+                text.push_str(&values_line("sv", &values));
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::load_classifier;
+    use crate::util::load_numeric_dataset;
+
+    fn labels(classes: &str) -> Vec<String> {
+        classes.split_whitespace().map(|class| class.to_string()).collect()
+    }
+
+    // Роздільна вибірка з відомою оптимальною площиною x1 = 1 (w = (1, 0), b = -1, усі точки на межі)
+    #[test]
+    fn smo_finds_maximum_margin() {
+        let x = vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![2.0, 0.0], vec![2.0, 1.0], vec![-1.0, 0.5], vec![3.0, 0.5]];
+        let y = labels("a a b b a b");
+        let mut model = SvmClassifier::new(1000.0).with_solver(SvmSolver::Smo).with_tolerance(1e-9);
+        model.fit(&x, &y);
+        let machine = &model.machines[0];
+        assert!((machine.weights[0] - 1.0).abs() < 1e-6 && machine.weights[1].abs() < 1e-6, "{:?}", machine.weights);
+        assert!((machine.bias + 1.0).abs() < 1e-6, "{}", machine.bias);
+        assert_eq!(model.predict(&x), y);
+    }
+
+    // XOR лінійно не роздільний, але з RBF-ядром і великим C усі точки мають зазор не менше 1
+    #[test]
+    fn smo_separates_xor_with_rbf_kernel() {
+        let x = vec![vec![0.0, 0.0], vec![1.0, 1.0], vec![0.0, 1.0], vec![1.0, 0.0]];
+        let y = labels("a a b b");
+        let mut model = SvmClassifier::new(1000.0).with_kernel(Kernel::Rbf(1.0)).with_tolerance(1e-9);
+        model.fit(&x, &y);
+        for (scores, label) in model.decision_function(&x).iter().zip(&y) {
+            let sign = if label == "b" { 1.0 } else { -1.0 };
+            assert!(sign * scores[0] >= 1.0 - 1e-6, "{scores:?} {label}");
+        }
+        assert_eq!(model.n_support(), vec![4]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let (x, y, _) = load_numeric_dataset("datasets/iris.csv", 1).unwrap();
+        let kernels = [Kernel::Linear, Kernel::Rbf(0.5), Kernel::Polynomial { degree: 2, gamma: 0.5, coef0: 1.0 }];
+        for kernel in kernels {
+            let mut model = SvmClassifier::new(1.0).with_kernel(kernel);
+            model.fit(&x, &y);
+            let text = Classifier::to_text(&model);
+            let restored = SvmClassifier::from_text(&text).unwrap();
+            assert_eq!(restored.decision_function(&x), model.decision_function(&x));
+            assert_eq!(Classifier::to_text(&restored), text);
+            assert_eq!(load_classifier(&text).unwrap().predict(&x), model.predict(&x));
+        }
+    }
+}
@@ -1,7 +1,11 @@
+use std::collections::HashMap;
 use csv::Reader;
 use regex::Regex;
 
-pub fn load_and_tokenize_dataset(path: &str) -> Result<(Vec<Vec<String>>, Vec<String>), Box<dyn std::error::Error>> {
+// (токени повідомлень, класи)
+pub type TextDataset = (Vec<Vec<String>>, Vec<String>);
+
+pub fn load_and_tokenize_dataset(path: &str) -> Result<TextDataset, Box<dyn std::error::Error>> {
     let mut x: Vec<Vec<String>> = Vec::new();
     let mut y: Vec<String> = Vec::new();
 
@@ -23,4 +27,175 @@ pub fn load_and_tokenize_dataset(path: &str) -> Result<(Vec<Vec<String>>, Vec<St
     }
 
     Ok((x, y))
-}
\ No newline at end of file
+}
+
+// Простий генератор псевдовипадкових чисел (SplitMix64), щоб результати були відтворюваними за seed
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // рівномірно в [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // рівномірно в [0, n)
+    pub fn gen_range(&mut self, n: usize) -> usize {
+        (self.next_f64() * n as f64) as usize % n.max(1)
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_range(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+// Скалярний добуток (зайві компоненти довшого вектора ігноруються)
+pub fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// Ймовірності softmax у місці, з відніманням максимуму
+pub fn softmax(scores: &mut [f64]) {
+    let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    scores.iter_mut().for_each(|s| *s = (*s - max).exp());
+    let sum: f64 = scores.iter().sum();
+    scores.iter_mut().for_each(|s| *s /= sum);
+}
+
+pub fn accuracy(y_true: &[String], y_pred: &[String]) -> f64 {
+    if y_true.is_empty() {
+        return 0.0;
+    }
+    let correct = y_true.iter().zip(y_pred.iter()).filter(|(a, b)| a == b).count();
+    correct as f64 / y_true.len() as f64
+}
+
+// Стратифікований поділ на train/test (повертає індекси), як train_test_split(stratify=y) у ноутбуці
+pub fn train_test_split_indices(y: &[String], test_size: f64, seed: u64) -> (Vec<usize>, Vec<usize>) {
+    let mut rng = Rng::new(seed);
+    let mut by_class: HashMap<&String, Vec<usize>> = HashMap::new();
+    for (i, label) in y.iter().enumerate() {
+        by_class.entry(label).or_default().push(i);
+    }
+    let mut classes: Vec<&String> = by_class.keys().cloned().collect();
+    classes.sort();
+
+    let mut train = Vec::new();
+    let mut test = Vec::new();
+    for class in classes {
+        let mut indices = by_class[class].clone();
+        rng.shuffle(&mut indices);
+        let n_test = (indices.len() as f64 * test_size).round() as usize;
+        test.extend_from_slice(&indices[..n_test]);
+        train.extend_from_slice(&indices[n_test..]);
+    }
+    rng.shuffle(&mut train);
+    rng.shuffle(&mut test);
+    (train, test)
+}
+
+// Стратифікований поділ на folds частин (StratifiedKFold): повертає (train, test) індекси для кожної частини
+pub fn stratified_k_fold(y: &[String], folds: usize, seed: u64) -> Vec<(Vec<usize>, Vec<usize>)> {
+    let folds = folds.max(2);
+    let mut rng = Rng::new(seed);
+    let mut by_class: HashMap<&String, Vec<usize>> = HashMap::new();
+    for (i, label) in y.iter().enumerate() {
+        by_class.entry(label).or_default().push(i);
+    }
+    let mut classes: Vec<&String> = by_class.keys().cloned().collect();
+    classes.sort();
+
+    // приклади кожного класу по черзі розкладаються по частинах
+    let mut fold_of = vec![0; y.len()];
+    let mut position = 0;
+    for class in classes {
+        let mut indices = by_class[class].clone();
+        rng.shuffle(&mut indices);
+        for i in indices {
+            fold_of[i] = position % folds;
+            position += 1;
+        }
+    }
+    (0..folds)
+        .map(|fold| {
+            let train = (0..y.len()).filter(|&i| fold_of[i] != fold).collect();
+            let test = (0..y.len()).filter(|&i| fold_of[i] == fold).collect();
+            (train, test)
+        })
+        .collect()
+}
+
+pub fn select_rows<T: Clone>(data: &[T], indices: &[usize]) -> Vec<T> {
+    indices.iter().map(|&i| data[i].clone()).collect()
+}
+
+// Застосовує f до кожного елемента, розбиваючи items на n_jobs суцільних частин (порядок результатів зберігається)
+pub fn parallel_map<T: Sync, R: Send>(items: &[T], n_jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let n_jobs = n_jobs.clamp(1, items.len().max(1));
+    if n_jobs == 1 {
+        return items.iter().map(f).collect();
+    }
+    let chunk_size = items.len().div_ceil(n_jobs);
+    let f = &f;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<R>>()))
+            .collect();
+        handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    })
+}
+
+// (ознаки, класи, назви ознак)
+pub type NumericDataset = (Vec<Vec<f64>>, Vec<String>, Vec<String>);
+
+// Зчитує числовий датасет: останній стовпець - клас, перші skip_columns стовпців (наприклад Id) пропускаються.
+// Рядки з порожніми/нечисловими значеннями відкидаються.
+pub fn load_numeric_dataset(path: &str, skip_columns: usize) -> Result<NumericDataset, Box<dyn std::error::Error>> {
+    let (x, y, feature_names) = load_numeric_dataset_with_missing(path, skip_columns)?;
+    let complete: Vec<usize> = (0..x.len()).filter(|&i| x[i].iter().all(|v| !v.is_nan())).collect();
+    Ok((select_rows(&x, &complete), select_rows(&y, &complete), feature_names))
+}
+
+// Те саме, але рядки з пропусками залишаються: порожні/нечислові значення стають NaN (для імпутації)
+pub fn load_numeric_dataset_with_missing(path: &str, skip_columns: usize) -> Result<NumericDataset, Box<dyn std::error::Error>> {
+    let mut x: Vec<Vec<f64>> = Vec::new();
+    let mut y: Vec<String> = Vec::new();
+
+    let mut reader = Reader::from_path(path)?;
+    let headers = reader.headers()?.clone();
+    let num_fields = headers.len();
+    let feature_names: Vec<String> = headers.iter()
+        .skip(skip_columns)
+        .take(num_fields - 1 - skip_columns)
+        .map(|s| s.to_string())
+        .collect();
+
+    for result in reader.records() {
+        let record = result?;
+        let features: Vec<f64> = record.iter()
+            .skip(skip_columns)
+            .take(num_fields - 1 - skip_columns)
+            .map(|val| val.trim().parse::<f64>().unwrap_or(f64::NAN))
+            .collect();
+        x.push(features);
+        y.push(record.get(num_fields - 1).unwrap().to_string());
+    }
+
+    Ok((x, y, feature_names))
+}